@@ -1,13 +1,24 @@
+use aoc_common::input::day_input;
 use criterion::{
-    criterion_group, criterion_main, Criterion,
+    criterion_group, criterion_main, BatchSize, Criterion,
 };
 use {{crate_name}}::*;
 
 fn criterion_benchmark_part1(c: &mut Criterion) {
-    let input = include_str!("../input1.txt");
+    let input = day_input(env!("CARGO_MANIFEST_DIR"), 1).unwrap();
 
     let mut group = c.benchmark_group("{{crate_name}}::part1");
-    group.bench_with_input("part1", input, |b, input| {
+    group.bench_with_input("parse", input.as_str(), |b, input| {
+        b.iter(|| part1::parse(input))
+    });
+    group.bench_with_input("solve", input.as_str(), |b, input| {
+        b.iter_batched(
+            || part1::parse(input).unwrap(),
+            part1::solve,
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_with_input("total", input.as_str(), |b, input| {
         b.iter(|| part1::process(input))
     });
 
@@ -15,10 +26,20 @@ fn criterion_benchmark_part1(c: &mut Criterion) {
 }
 
 fn criterion_benchmark_part2(c: &mut Criterion) {
-    let input = include_str!("../input2.txt");
+    let input = day_input(env!("CARGO_MANIFEST_DIR"), 2).unwrap();
 
     let mut group = c.benchmark_group("{{crate_name}}::part2");
-    group.bench_with_input("part2", input, |b, input| {
+    group.bench_with_input("parse", input.as_str(), |b, input| {
+        b.iter(|| part2::parse(input))
+    });
+    group.bench_with_input("solve", input.as_str(), |b, input| {
+        b.iter_batched(
+            || part2::parse(input).unwrap(),
+            part2::solve,
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_with_input("total", input.as_str(), |b, input| {
         b.iter(|| part2::process(input))
     });
 
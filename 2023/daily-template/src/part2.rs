@@ -1,21 +1,33 @@
-use crate::custom_error::AocError;
+use aoc_common::{trace::detail, AocError};
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 pub fn process(
-    _input: &str,
+    input: &str,
 ) -> miette::Result<String, AocError> {
+    solve(parse(input)?)
+}
+
+/// Read the puzzle input.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<&str, AocError> {
+    Ok(input)
+}
+
+/// Compute the answer from the parsed input.
+#[tracing::instrument(skip_all)]
+pub fn solve(_input: &str) -> miette::Result<String, AocError> {
     todo!("day 01 - part 1");
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        todo!("haven't built test yet");
-        let input = "";
-        assert_eq!("", process(input)?);
+    #[rstest]
+    #[case("", "")]
+    fn test_process(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(input)?);
         Ok(())
     }
-}
\ No newline at end of file
+}
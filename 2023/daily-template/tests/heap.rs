@@ -0,0 +1,5 @@
+aoc_common::check_heap!({{crate_name}}, size: 100, {
+    // lower these to about a quarter above what each part allocates once it is solved
+    part1: { peak_bytes: 1 << 20, total_blocks: 10_000 },
+    part2: { peak_bytes: 1 << 20, total_blocks: 10_000 },
+});
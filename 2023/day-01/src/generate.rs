@@ -0,0 +1,76 @@
+use aoc_common::generate::{self, Rng};
+
+const SPELLED: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// `size` lines of calibration document, each with letters around one to four digits,
+/// at least one of them numeric.
+pub fn input(seed: u64, size: usize) -> String {
+    let mut rng = generate::rng(seed);
+    (0..size)
+        .map(|_| line(&mut rng))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn line(rng: &mut Rng) -> String {
+    let digits = rng.usize(1..=4);
+    let numeric = rng.usize(..digits);
+    let mut line = String::new();
+    for i in 0..digits {
+        let len = rng.usize(..6);
+        line.push_str(&generate::word(rng, b"abcdefghijklmnopqrstuvwxyz", len));
+        let digit = rng.usize(..9);
+        if i == numeric || rng.bool() {
+            line.push(char::from(b'1' + digit as u8));
+        } else {
+            line.push_str(SPELLED[digit]);
+        }
+    }
+    let len = rng.usize(..4);
+    line.push_str(&generate::word(rng, b"abcdefghijklmnopqrstuvwxyz", len));
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    // the calibration values of both parts, trying every digit at every position of a line
+    fn reference(input: &str) -> (u32, u32) {
+        let value = |digits: Vec<u32>| digits[0] * 10 + digits[digits.len() - 1];
+        let mut sums = (0, 0);
+        for line in input.lines() {
+            let mut numeric = Vec::new();
+            let mut spelled = Vec::new();
+            for (i, c) in line.char_indices() {
+                if let Some(digit) = c.to_digit(10) {
+                    numeric.push(digit);
+                    spelled.push(digit);
+                }
+                for (digit, word) in (1..).zip(SPELLED) {
+                    if line[i..].starts_with(word) {
+                        spelled.push(digit);
+                    }
+                }
+            }
+            sums.0 += value(numeric);
+            sums.1 += value(spelled);
+        }
+        sums
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(100)]
+    #[case(1000)]
+    fn test_solves_generated_input(#[case] size: usize) -> miette::Result<()> {
+        let input = input(generate::SEED, size);
+        let (part1, part2) = reference(&input);
+        assert_eq!(part1.to_string(), crate::part1::process(&input)?);
+        assert_eq!(part2.to_string(), crate::part2::process(&input)?);
+        Ok(())
+    }
+}
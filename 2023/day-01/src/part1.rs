@@ -1,23 +1,35 @@
 use nom::{character::complete::anychar, combinator::iterator, IResult};
 
-use crate::custom_error::AocError;
+use aoc_common::{trace::detail, AocError};
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let output = input
+    solve(parse(input)?)
+}
+
+/// Read the calibration value of each line.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<Vec<u32>, AocError> {
+    input
         .lines()
-        .map(process_line)
-        .sum::<miette::Result<u32, AocError>>()?;
+        .map(|line| process_line(input, line))
+        .collect()
+}
+
+/// Sum the calibration values.
+#[tracing::instrument(skip_all)]
+pub fn solve(values: Vec<u32>) -> miette::Result<String, AocError> {
+    let output = values.iter().sum::<u32>();
     Ok(output.to_string())
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 fn parse_digit(input: &str) -> IResult<&str, Option<u32>> {
     let (input, digit) = anychar(input)?;
     Ok((input, digit.to_digit(10)))
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 fn parser(input: &str) -> IResult<&str, Vec<u32>> {
     let mut it = iterator(input, parse_digit);
     let output = it.flatten().collect();
@@ -25,11 +37,13 @@ fn parser(input: &str) -> IResult<&str, Vec<u32>> {
     Ok((input, output))
 }
 
-#[tracing::instrument]
-pub fn process_line(line: &str) -> miette::Result<u32, AocError> {
-    let (_, result) = parser(line).expect("failed to parse line");
+#[tracing::instrument(skip(input, line), fields(line = detail(line)))]
+pub fn process_line(input: &str, line: &str) -> miette::Result<u32, AocError> {
+    let (_, result) = parser(line).map_err(|e| AocError::from_nom(input, e))?;
     let mut it = result.iter();
-    let first = it.next().expect("must have at least one digit");
+    let first = it
+        .next()
+        .ok_or_else(|| AocError::parse_error(input, line, "at least one digit"))?;
     let last = it.last().unwrap_or(first);
     let result = first * 10 + last;
     Ok(result)
@@ -47,7 +61,7 @@ mod tests {
     #[case("a1b2c3d4e5f", 15)]
     #[case("treb7uchet", 77)]
     fn test_process_line(#[case] input: &str, #[case] expected: u32) {
-        assert_eq!(expected, process_line(input).unwrap());
+        assert_eq!(expected, process_line(input, input).unwrap());
     }
 
     #[test]
@@ -60,4 +74,16 @@ treb7uchet";
         assert_eq!("142", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_process_line_without_digit() {
+        let input = "1abc2\npqrstuvwx";
+        match process(input) {
+            Err(AocError::ParseError(e)) => {
+                assert_eq!(aoc_common::Location { line: 2, column: 1 }, e.location);
+                assert_eq!("at least one digit", e.expected);
+            }
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
 }
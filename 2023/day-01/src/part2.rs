@@ -6,24 +6,36 @@ use nom::{
     IResult,
 };
 
-use crate::custom_error::AocError;
+use aoc_common::{trace::detail, AocError};
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let output = input
+    solve(parse(input)?)
+}
+
+/// Read the calibration value of each line.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<Vec<u32>, AocError> {
+    input
         .lines()
-        .map(process_line)
-        .sum::<miette::Result<u32, AocError>>()?;
+        .map(|line| process_line(input, line))
+        .collect()
+}
+
+/// Sum the calibration values.
+#[tracing::instrument(skip_all)]
+pub fn solve(values: Vec<u32>) -> miette::Result<String, AocError> {
+    let output = values.iter().sum::<u32>();
     Ok(output.to_string())
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 fn maybe_digit(input: &str) -> IResult<&str, Option<u32>> {
     let (rest, c) = anychar(input)?;
     Ok((rest, c.to_digit(10)))
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 fn parse_number(input: &str) -> IResult<&str, Option<u32>> {
     let res: IResult<&str, u32> = alt((
         value(1, tag("one")),
@@ -44,7 +56,7 @@ fn parse_number(input: &str) -> IResult<&str, Option<u32>> {
     Ok((input, digit_from_letters.or(digit)))
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 fn parser(input: &str) -> IResult<&str, Vec<u32>> {
     let mut it = iterator(input, parse_number);
     let output = it.flatten().collect();
@@ -52,11 +64,13 @@ fn parser(input: &str) -> IResult<&str, Vec<u32>> {
     Ok((input, output))
 }
 
-#[tracing::instrument]
-pub fn process_line(line: &str) -> miette::Result<u32, AocError> {
-    let (_, result) = parser(line).expect("failed to parse line");
+#[tracing::instrument(skip(input, line), fields(line = detail(line)))]
+pub fn process_line(input: &str, line: &str) -> miette::Result<u32, AocError> {
+    let (_, result) = parser(line).map_err(|e| AocError::from_nom(input, e))?;
     let mut it = result.iter();
-    let first = it.next().expect("must have at least one digit");
+    let first = it
+        .next()
+        .ok_or_else(|| AocError::parse_error(input, line, "at least one digit"))?;
     let last = it.last().unwrap_or(first);
     let result = first * 10 + last;
     Ok(result)
@@ -73,7 +87,7 @@ mod tests {
     #[case("eightwothree", 83)]
     #[case("4oneight", 48)] // overlapping case
     fn test_process_line(#[case] input: &str, #[case] expected: u32) {
-        assert_eq!(expected, process_line(input).unwrap());
+        assert_eq!(expected, process_line(input, input).unwrap());
     }
 
     #[test]
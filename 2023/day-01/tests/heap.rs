@@ -0,0 +1,6 @@
+aoc_common::check_heap!(day_01, size: 1000, {
+    // 4_112 bytes at the peak, 1_010 allocations when measured
+    part1: { peak_bytes: 5_200, total_blocks: 1_300 },
+    // 4_128 bytes at the peak, 1_011 allocations when measured
+    part2: { peak_bytes: 5_200, total_blocks: 1_300 },
+});
@@ -0,0 +1,65 @@
+use aoc_common::generate::{self, Rng};
+
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+
+/// `size` games of one to six draws of up to 20 cubes of each color.
+pub fn input(seed: u64, size: usize) -> String {
+    let mut rng = generate::rng(seed);
+    (1..=size)
+        .map(|id| {
+            let draws = (0..rng.usize(1..=6))
+                .map(|_| draw(&mut rng))
+                .collect::<Vec<_>>();
+            format!("Game {id}: {}", draws.join("; "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn draw(rng: &mut Rng) -> String {
+    let mut colors = COLORS.to_vec();
+    rng.shuffle(&mut colors);
+    colors.truncate(rng.usize(1..=3));
+    colors
+        .iter()
+        .map(|color| format!("{} {color}", rng.u32(1..=20)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    // the possible games and the powers of the fewest cubes, draw by draw
+    fn reference(input: &str) -> (usize, u32) {
+        let mut sums = (0, 0);
+        for (id, line) in (1..).zip(input.lines()) {
+            let (_, draws) = line.split_once(": ").unwrap();
+            let mut fewest = [0; 3];
+            for cubes in draws.split("; ").flat_map(|draw| draw.split(", ")) {
+                let (count, color) = cubes.split_once(' ').unwrap();
+                let color = COLORS.iter().position(|c| *c == color).unwrap();
+                fewest[color] = fewest[color].max(count.parse().unwrap());
+            }
+            if fewest[0] <= 12 && fewest[1] <= 13 && fewest[2] <= 14 {
+                sums.0 += id;
+            }
+            sums.1 += fewest.iter().product::<u32>();
+        }
+        sums
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(100)]
+    #[case(1000)]
+    fn test_solves_generated_input(#[case] size: usize) -> miette::Result<()> {
+        let input = input(generate::SEED, size);
+        let (part1, part2) = reference(&input);
+        assert_eq!(part1.to_string(), crate::part1::process(&input)?);
+        assert_eq!(part2.to_string(), crate::part2::process(&input)?);
+        Ok(())
+    }
+}
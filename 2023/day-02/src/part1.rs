@@ -7,7 +7,7 @@ use nom::{
     IResult,
 };
 
-use crate::custom_error::AocError;
+use aoc_common::{trace::detail, AocError};
 use std::collections::HashMap;
 
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
@@ -49,7 +49,7 @@ impl Bag {
 }
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     id: u32,
     draws: Vec<Draw>,
 }
@@ -65,14 +65,19 @@ impl Game {
     }
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(parse(input)?)
+}
+
+/// Sum the ids of the games possible with 12 red, 13 green and 14 blue cubes.
+#[tracing::instrument(skip_all)]
+pub fn solve(games: Vec<Game>) -> miette::Result<String, AocError> {
     let mut bag = Bag::new();
     let bag = bag
         .add(Color::Red, 12)
         .add(Color::Blue, 14)
         .add(Color::Green, 13);
-    let games = parse(input)?;
     let impossible_games = games
         .iter()
         .filter(|game| game.impossible_draw(bag).is_none())
@@ -81,10 +86,12 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
     Ok(impossible_games.iter().sum::<u32>().to_string())
 }
 
-fn parse(input: &str) -> miette::Result<Vec<Game>, AocError> {
+/// Read the draws of each game.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<Vec<Game>, AocError> {
     let games = input
         .lines()
-        .map(parse_line)
+        .map(|line| parse_line(input, line))
         .collect::<miette::Result<Vec<_>, _>>()?;
     Ok(games)
 }
@@ -122,8 +129,8 @@ fn parse_draws(input: &str) -> IResult<&str, Vec<Draw>> {
     Ok((input, draws))
 }
 
-fn parse_line(line: &str) -> miette::Result<Game, AocError> {
-    let (_, game) = parse_game(line).expect("cannot parse game");
+fn parse_line(input: &str, line: &str) -> miette::Result<Game, AocError> {
+    let (_, game) = parse_game(line).map_err(|e| AocError::from_nom(input, e))?;
     Ok(game)
 }
 
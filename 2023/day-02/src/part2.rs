@@ -7,7 +7,7 @@ use nom::{
     IResult,
 };
 
-use crate::custom_error::AocError;
+use aoc_common::{trace::detail, AocError};
 use std::collections::HashMap;
 
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
@@ -40,12 +40,16 @@ impl Bag {
     }
 
     fn power_set(&self) -> u32 {
-        self.cubes.values().product()
+        // a color never drawn needs no cube of it
+        [Color::Red, Color::Blue, Color::Green]
+            .iter()
+            .map(|color| self.cubes.get(color).copied().unwrap_or(0))
+            .product()
     }
 }
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     draws: Vec<Draw>,
 }
 
@@ -57,18 +61,25 @@ impl Game {
     }
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let games = parse(input)?;
+    solve(parse(input)?)
+}
+
+/// Sum the powers of the smallest bag of each game.
+#[tracing::instrument(skip_all)]
+pub fn solve(games: Vec<Game>) -> miette::Result<String, AocError> {
     let smallest_bags = games.iter().map(|game| game.smallest_bag());
     let power_sets = smallest_bags.map(|bag| bag.power_set());
     Ok(power_sets.sum::<u32>().to_string())
 }
 
-fn parse(input: &str) -> miette::Result<Vec<Game>, AocError> {
+/// Read the draws of each game.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<Vec<Game>, AocError> {
     let games = input
         .lines()
-        .map(parse_line)
+        .map(|line| parse_line(input, line))
         .collect::<miette::Result<Vec<_>, _>>()?;
     Ok(games)
 }
@@ -106,8 +117,8 @@ fn parse_draws(input: &str) -> IResult<&str, Vec<Draw>> {
     Ok((input, draws))
 }
 
-fn parse_line(line: &str) -> miette::Result<Game, AocError> {
-    let (_, game) = parse_game(line).expect("cannot parse game");
+fn parse_line(input: &str, line: &str) -> miette::Result<Game, AocError> {
+    let (_, game) = parse_game(line).map_err(|e| AocError::from_nom(input, e))?;
     Ok(game)
 }
 
@@ -131,6 +142,15 @@ mod tests {
         assert_eq!(bag.cubes[&Color::Green], 2);
     }
 
+    #[rstest]
+    #[case("Game 1: 3 blue, 4 red; 2 green", 24)]
+    #[case("Game 1: 3 blue, 4 red; 1 blue", 0)]
+    fn test_power_set(#[case] line: &str, #[case] expected: u32) -> miette::Result<()> {
+        let game = parse_line(line, line)?;
+        assert_eq!(expected, game.smallest_bag().power_set());
+        Ok(())
+    }
+
     #[rstest]
     #[case("3 red", (Color::Red, 3))]
     #[case("4 blue", (Color::Blue, 4))]
@@ -0,0 +1,6 @@
+aoc_common::check_heap!(day_02, size: 1000, {
+    // 476_245 bytes at the peak, 8_497 allocations when measured
+    part1: { peak_bytes: 600_000, total_blocks: 11_000 },
+    // 467_024 bytes at the peak, 9_489 allocations when measured
+    part2: { peak_bytes: 590_000, total_blocks: 12_000 },
+});
@@ -0,0 +1,96 @@
+use aoc_common::generate;
+
+const SYMBOLS: &[u8] = b"#+$/@=%-&";
+
+/// A square engine schematic of `size` rows and columns, with numbers of up to three
+/// digits and symbols scattered among the dots.
+pub fn input(seed: u64, size: usize) -> String {
+    let mut rng = generate::rng(seed);
+    let mut rows = Vec::with_capacity(size);
+    for _ in 0..size {
+        let mut row = String::with_capacity(size);
+        while row.len() < size {
+            let left = size - row.len();
+            match rng.u8(..10) {
+                // a number is always followed by something else
+                0..=2 if left > 1 => {
+                    let digits = rng.usize(1..=3.min(left - 1));
+                    let number = rng.u32(10u32.pow(digits as u32 - 1)..10u32.pow(digits as u32));
+                    row.push_str(&number.to_string());
+                    row.push('.');
+                }
+                // gears make half of the symbols
+                3 if rng.bool() => row.push('*'),
+                3 => row.push(char::from(SYMBOLS[rng.usize(..SYMBOLS.len())])),
+                _ => row.push('.'),
+            }
+        }
+        rows.push(row);
+    }
+    rows.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::collections::HashMap;
+
+    // the part numbers and the gear ratios, looking around each number cell by cell
+    fn reference(input: &str) -> (u32, u32) {
+        let grid = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+        let mut part_numbers = 0;
+        let mut gears = HashMap::<(usize, usize), Vec<u32>>::new();
+        for (row, line) in grid.iter().enumerate() {
+            let mut column = 0;
+            while column < line.len() {
+                if !line[column].is_ascii_digit() {
+                    column += 1;
+                    continue;
+                }
+                let start = column;
+                while column < line.len() && line[column].is_ascii_digit() {
+                    column += 1;
+                }
+                let number = std::str::from_utf8(&line[start..column])
+                    .unwrap()
+                    .parse::<u32>()
+                    .unwrap();
+                let mut is_part = false;
+                // the cells around the number, within the grid
+                let (top, left) = (row.saturating_sub(1), start.saturating_sub(1));
+                for (r, cells) in grid.iter().enumerate().skip(top).take(row + 2 - top) {
+                    for (c, &cell) in cells.iter().enumerate().skip(left).take(column + 1 - left) {
+                        if cell != b'.' && !cell.is_ascii_digit() {
+                            is_part = true;
+                        }
+                        if cell == b'*' {
+                            gears.entry((r, c)).or_default().push(number);
+                        }
+                    }
+                }
+                if is_part {
+                    part_numbers += number;
+                }
+            }
+        }
+        let ratios = gears
+            .values()
+            .filter(|numbers| numbers.len() == 2)
+            .map(|numbers| numbers[0] * numbers[1])
+            .sum();
+        (part_numbers, ratios)
+    }
+
+    #[rstest]
+    #[case(10)]
+    #[case(100)]
+    #[case(140)]
+    fn test_solves_generated_input(#[case] size: usize) -> miette::Result<()> {
+        let input = input(generate::SEED, size);
+        let (part1, part2) = reference(&input);
+        assert_eq!(part1.to_string(), crate::part1::process(&input)?);
+        assert_eq!(part2.to_string(), crate::part2::process(&input)?);
+        Ok(())
+    }
+}
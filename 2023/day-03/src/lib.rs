@@ -1,6 +1,13 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 #![allow(unused_variables)]
-pub mod custom_error;
+pub mod generate;
 pub mod part1;
 pub mod part2;
+
+aoc_common::solutions! {
+    year: 2023,
+    day: 3,
+    1 => part1::process,
+    2 => part2::process,
+}
@@ -1,5 +1,5 @@
 
-use crate::custom_error::AocError;
+use aoc_common::{trace::detail, AocError};
 use nom::{self, InputIter, InputLength, branch::alt, combinator::{value, map}, multi::many1};
 use nom_locate::{position, LocatedSpan};
 use quadtree_rs::{
@@ -89,7 +89,7 @@ fn get_adjacent_symbols<'a>(element: &Located<Element>, symbols: &'a SymbolQueri
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct Schematics {
+pub struct Schematics {
     pieces: Vec<Located<Element>>,
     symbols: SymbolQuerier,
 }
@@ -155,9 +155,22 @@ fn parse_schematics(s: Span) -> nom::IResult<Span, Schematics> {
     Ok((s, schematics))
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, schematics) = parse_schematics(Span::new(input)).map_err(|e| AocError::ParseError(e.to_string()))?;
+    solve(parse(input)?)
+}
+
+/// Read the numbers and symbols of the engine schematic, by position.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<Schematics, AocError> {
+    let (_, schematics) = parse_schematics(Span::new(input))
+        .map_err(|e| AocError::from_nom(input, e))?;
+    Ok(schematics)
+}
+
+/// Sum the numbers adjacent to a symbol.
+#[tracing::instrument(skip_all)]
+pub fn solve(schematics: Schematics) -> miette::Result<String, AocError> {
     let engine_parts = schematics.get_engine_parts();
     let result = engine_parts.iter().sum::<u32>().to_string();
     Ok(result)
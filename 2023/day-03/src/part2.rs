@@ -1,5 +1,5 @@
 
-use crate::custom_error::AocError;
+use aoc_common::{trace::detail, AocError};
 use nom::{self, InputIter, InputLength, branch::alt, combinator::{value, map}, multi::many1};
 use nom_locate::{position, LocatedSpan};
 use quadtree_rs::{
@@ -95,7 +95,7 @@ fn get_adjacent_parts<'a>(position: &Point<u32>, parts: &'a PartQuerier) -> Vec<
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct Schematics {
+pub struct Schematics {
     gears: Vec<Point<u32>>,
     parts: PartQuerier,
 }
@@ -158,9 +158,22 @@ fn parse_schematics(s: Span) -> nom::IResult<Span, Schematics> {
     Ok((s, schematics))
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, schematics) = parse_schematics(Span::new(input)).map_err(|e| AocError::ParseError(e.to_string()))?;
+    solve(parse(input)?)
+}
+
+/// Read the numbers and symbols of the engine schematic, by position.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<Schematics, AocError> {
+    let (_, schematics) = parse_schematics(Span::new(input))
+        .map_err(|e| AocError::from_nom(input, e))?;
+    Ok(schematics)
+}
+
+/// Sum the ratios of the gears.
+#[tracing::instrument(skip_all)]
+pub fn solve(schematics: Schematics) -> miette::Result<String, AocError> {
     let result = schematics.get_gears_ratios().iter().sum::<u32>().to_string();
     Ok(result)
 }
@@ -0,0 +1,6 @@
+aoc_common::check_heap!(day_03, size: 100, {
+    // 802_000 bytes at the peak, 19_451 allocations when measured
+    part1: { peak_bytes: 1_100_000, total_blocks: 25_000 },
+    // 1_621_760 bytes at the peak, 20_065 allocations when measured
+    part2: { peak_bytes: 2_100_000, total_blocks: 26_000 },
+});
@@ -0,0 +1,79 @@
+use aoc_common::generate;
+
+/// `size` scratchcards of 10 winning numbers and 25 numbers we have, most of them winning
+/// nothing so that the copies of part two stay bounded.
+pub fn input(seed: u64, size: usize) -> String {
+    let mut rng = generate::rng(seed);
+    let mut input = String::new();
+    for index in 1..=size {
+        let numbers = generate::distinct(&mut rng, 1..100, 35);
+        let (winning, others) = numbers.split_at(10);
+        let matches = if rng.u8(..4) == 0 {
+            rng.usize(1..=3)
+        } else {
+            0
+        };
+        let mut have = winning[..matches].to_vec();
+        have.extend_from_slice(&others[..25 - matches]);
+        rng.shuffle(&mut have);
+
+        let format = |numbers: &[u32]| {
+            numbers
+                .iter()
+                .map(|number| format!("{number:>2}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        input.push_str(&format!(
+            "Card {index:>3}: {} | {}\n",
+            format(winning),
+            format(&have)
+        ));
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    // the points of the cards, and the copies won card by card
+    fn reference(input: &str) -> (u32, u32) {
+        let matches = input
+            .lines()
+            .map(|line| {
+                let (_, numbers) = line.split_once(": ").unwrap();
+                let (winning, have) = numbers.split_once(" | ").unwrap();
+                let winning = winning.split_whitespace().collect::<Vec<_>>();
+                have.split_whitespace()
+                    .filter(|number| winning.contains(number))
+                    .count()
+            })
+            .collect::<Vec<_>>();
+        let points = matches
+            .iter()
+            .filter(|&&count| count > 0)
+            .map(|count| 1 << (count - 1))
+            .sum();
+        let mut copies = vec![1; matches.len()];
+        for (card, count) in matches.iter().enumerate() {
+            for next in card + 1..(card + 1 + count).min(matches.len()) {
+                copies[next] += copies[card];
+            }
+        }
+        (points, copies.iter().sum())
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(100)]
+    #[case(1000)]
+    fn test_solves_generated_input(#[case] size: usize) -> miette::Result<()> {
+        let input = input(generate::SEED, size);
+        let (part1, part2) = reference(&input);
+        assert_eq!(part1.to_string(), crate::part1::process(&input)?);
+        assert_eq!(part2.to_string(), crate::part2::process(&input)?);
+        Ok(())
+    }
+}
@@ -9,10 +9,10 @@ use nom::{
     IResult,
 };
 
-use crate::custom_error::AocError;
+use aoc_common::{trace::detail, AocError};
 
 #[derive(Debug, PartialEq, Eq)]
-struct Card {
+pub struct Card {
     index: u32,
     winning: HashSet<u32>,
     have: HashSet<u32>,
@@ -72,9 +72,21 @@ fn parse_cards(input: &str) -> IResult<&str, Vec<Card>> {
     parser(input)
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, cards) = parse_cards(input).map_err(|e| AocError::ParseError(e.to_string()))?;
+    solve(parse(input)?)
+}
+
+/// Read the winning numbers and the numbers we have of each card.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<Vec<Card>, AocError> {
+    let (_, cards) = parse_cards(input).map_err(|e| AocError::from_nom(input, e))?;
+    Ok(cards)
+}
+
+/// Sum the scores of the cards.
+#[tracing::instrument(skip_all)]
+pub fn solve(cards: Vec<Card>) -> miette::Result<String, AocError> {
     let total_score = cards.iter().map(|card| card.score()).sum::<u32>();
     Ok(total_score.to_string())
 }
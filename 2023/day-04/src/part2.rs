@@ -9,10 +9,10 @@ use nom::{
     IResult,
 };
 
-use crate::custom_error::AocError;
+use aoc_common::{trace::detail, AocError};
 
 #[derive(Debug, PartialEq, Eq)]
-struct Card {
+pub struct Card {
     winning: HashSet<u32>,
     have: HashSet<u32>,
 }
@@ -27,7 +27,7 @@ impl Card {
     }
 }
 
-type Deck = Vec<Card>;
+pub type Deck = Vec<Card>;
 
 // process_deck takes a deck of cards and counts the number of winning scratchcards
 fn process_deck(deck: Deck) -> u32 {
@@ -82,9 +82,21 @@ fn parse_deck(input: &str) -> IResult<&str, Deck> {
     separated_list0(line_ending, parse_card)(input)
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, deck) = parse_deck(input).map_err(|e| AocError::ParseError(e.to_string()))?;
+    solve(parse(input)?)
+}
+
+/// Read the winning numbers and the numbers we have of each card.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<Deck, AocError> {
+    let (_, deck) = parse_deck(input).map_err(|e| AocError::from_nom(input, e))?;
+    Ok(deck)
+}
+
+/// Count the cards won in total, originals included.
+#[tracing::instrument(skip_all)]
+pub fn solve(deck: Deck) -> miette::Result<String, AocError> {
     Ok(process_deck(deck).to_string())
 }
 
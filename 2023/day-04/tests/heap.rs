@@ -0,0 +1,6 @@
+aoc_common::check_heap!(day_04, size: 1000, {
+    // 378_624 bytes at the peak, 9_010 allocations when measured
+    part1: { peak_bytes: 480_000, total_blocks: 12_000 },
+    // 374_304 bytes at the peak, 9_011 allocations when measured
+    part2: { peak_bytes: 470_000, total_blocks: 12_000 },
+});
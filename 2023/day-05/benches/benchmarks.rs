@@ -1,22 +1,69 @@
-use day_05::*;
-
 fn main() {
-    // Run registered benchmarks.
+    // Run registered benchmarks, each part timed as parse, solve and total on the puzzle
+    // input, and on generated inputs of growing sizes.
+    #[cfg(feature = "tracy")]
+    aoc_common::trace::init(None);
     divan::main();
 }
 
-#[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!(
-        "../input1.txt",
-    )))
-    .unwrap();
+mod part1 {
+    use aoc_common::{generate::SEED, input::day_input};
+    use day_05::{generate, part1};
+
+    #[divan::bench]
+    fn parse(bencher: divan::Bencher) {
+        let input = day_input(env!("CARGO_MANIFEST_DIR"), 1).unwrap();
+        bencher.bench(|| part1::parse(divan::black_box(&input)).unwrap());
+    }
+
+    #[divan::bench]
+    fn solve(bencher: divan::Bencher) {
+        let input = day_input(env!("CARGO_MANIFEST_DIR"), 1).unwrap();
+        bencher
+            .with_inputs(|| part1::parse(&input).unwrap())
+            .bench_values(|parsed| part1::solve(parsed).unwrap());
+    }
+
+    #[divan::bench]
+    fn total(bencher: divan::Bencher) {
+        let input = day_input(env!("CARGO_MANIFEST_DIR"), 1).unwrap();
+        bencher.bench(|| part1::process(divan::black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(consts = [10, 100, 1_000])]
+    fn scaling<const SIZE: usize>(bencher: divan::Bencher) {
+        let input = generate::input(SEED, SIZE);
+        bencher.bench(|| part1::process(divan::black_box(&input)).unwrap());
+    }
 }
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input2.txt",
-    )))
-    .unwrap();
-}
\ No newline at end of file
+mod part2 {
+    use aoc_common::{generate::SEED, input::day_input};
+    use day_05::{generate, part2};
+
+    #[divan::bench]
+    fn parse(bencher: divan::Bencher) {
+        let input = day_input(env!("CARGO_MANIFEST_DIR"), 2).unwrap();
+        bencher.bench(|| part2::parse(divan::black_box(&input)).unwrap());
+    }
+
+    #[divan::bench]
+    fn solve(bencher: divan::Bencher) {
+        let input = day_input(env!("CARGO_MANIFEST_DIR"), 2).unwrap();
+        bencher
+            .with_inputs(|| part2::parse(&input).unwrap())
+            .bench_values(|parsed| part2::solve(parsed).unwrap());
+    }
+
+    #[divan::bench]
+    fn total(bencher: divan::Bencher) {
+        let input = day_input(env!("CARGO_MANIFEST_DIR"), 2).unwrap();
+        bencher.bench(|| part2::process(divan::black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(consts = [10, 100, 1_000])]
+    fn scaling<const SIZE: usize>(bencher: divan::Bencher) {
+        let input = generate::input(SEED, SIZE);
+        bencher.bench(|| part2::process(divan::black_box(&input)).unwrap());
+    }
+}
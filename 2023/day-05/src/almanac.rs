@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, line_ending, space0},
+    combinator::{map, map_res},
+    multi::{many1, separated_list0},
+    sequence::{preceded, terminated, tuple},
+    IResult,
+};
+use strum::EnumString;
+
+use aoc_common::{AocError, RangeMap, Segment};
+
+#[derive(EnumString, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[strum(serialize_all = "lowercase")]
+pub enum Entity {
+    Seed,
+    Soil,
+    Fertilizer,
+    Water,
+    Light,
+    Temperature,
+    Humidity,
+    Location,
+}
+
+// destination start, source start, length
+type RawMapping = (u64, u64, u64);
+
+#[derive(Debug)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    maps: BTreeMap<Entity, (Entity, RangeMap<u64>)>,
+}
+
+impl Almanac {
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let (_, (seeds, raw_maps)) =
+            parse_almanac(input).map_err(|e| AocError::from_nom(input, e))?;
+        let mut maps = BTreeMap::new();
+        for (from, to, mappings) in raw_maps {
+            let segments = mappings
+                .into_iter()
+                .map(|(dest, source, length)| Segment::new(source, dest, length))
+                .collect::<Result<Vec<_>, _>>()?;
+            maps.insert(from, (to, RangeMap::new(segments)?));
+        }
+        Ok(Self { seeds, maps })
+    }
+
+    /// Compose every map along the way from seeds to locations.
+    #[tracing::instrument(skip(self))]
+    pub fn seed_to_location(&self) -> Result<RangeMap<u64>, AocError> {
+        let mut entity = Entity::Seed;
+        let mut composed = RangeMap::identity();
+        // each map is followed at most once
+        for _ in 0..self.maps.len() {
+            if entity == Entity::Location {
+                break;
+            }
+            let (next, map) = self.maps.get(&entity).ok_or_else(|| {
+                AocError::LogicError(format!("no map from {entity:?} towards locations"))
+            })?;
+            composed = composed.then(map);
+            entity = *next;
+        }
+        if entity != Entity::Location {
+            return Err(AocError::LogicError(
+                "maps loop without reaching locations".to_string(),
+            ));
+        }
+        Ok(composed)
+    }
+}
+
+fn parse_number(input: &str) -> IResult<&str, u64> {
+    preceded(space0, nom::character::complete::u64)(input)
+}
+
+fn parse_mapping(input: &str) -> IResult<&str, RawMapping> {
+    tuple((parse_number, parse_number, parse_number))(input)
+}
+
+fn parse_entity(input: &str) -> IResult<&str, Entity> {
+    map_res(alpha1, |s: &str| s.parse())(input)
+}
+
+fn parse_map(input: &str) -> IResult<&str, (Entity, Entity, Vec<RawMapping>)> {
+    let (input, entity1) = parse_entity(input)?;
+    let (input, _) = tag("-to-")(input)?;
+    let (input, entity2) = parse_entity(input)?;
+    let (input, _) = terminated(tag(" map:"), line_ending)(input)?;
+    map(
+        separated_list0(line_ending, parse_mapping),
+        move |mappings| (entity1, entity2, mappings),
+    )(input)
+}
+
+type RawAlmanac = (Vec<u64>, Vec<(Entity, Entity, Vec<RawMapping>)>);
+
+fn parse_almanac(input: &str) -> IResult<&str, RawAlmanac> {
+    let (input, seeds) = preceded(tag("seeds:"), many1(parse_number))(input)?;
+    let (input, _) = line_ending(input)?;
+    let (input, _) = line_ending(input)?;
+    let (input, maps) = separated_list0(many1(line_ending), parse_map)(input)?;
+    Ok((input, (seeds, maps)))
+}
+
+#[cfg(test)]
+pub(crate) const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(79, 82)]
+    #[case(14, 43)]
+    #[case(55, 86)]
+    #[case(13, 35)]
+    fn test_seed_to_location(#[case] seed: u64, #[case] location: u64) -> miette::Result<()> {
+        let almanac = Almanac::parse(EXAMPLE)?;
+        assert_eq!(location, almanac.seed_to_location()?.get(seed));
+        Ok(())
+    }
+
+    #[test]
+    fn test_values_beyond_u32() -> miette::Result<()> {
+        let input = "seeds: 4294967295
+
+seed-to-location map:
+10 4000000000 500000000";
+        let almanac = Almanac::parse(input)?;
+        assert_eq!(294_967_305, almanac.seed_to_location()?.get(4_294_967_295));
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_map() -> miette::Result<()> {
+        let input = "seeds: 1
+
+seed-to-soil map:
+1 2 3";
+        let almanac = Almanac::parse(input)?;
+        assert!(matches!(
+            almanac.seed_to_location(),
+            Err(AocError::LogicError(_))
+        ));
+        Ok(())
+    }
+}
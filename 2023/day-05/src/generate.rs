@@ -0,0 +1,126 @@
+use aoc_common::generate::{self, Rng};
+
+const ENTITIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+// values stay below this, as in the puzzle
+const LIMIT: u64 = 1 << 32;
+
+/// An almanac of `size` seed ranges and maps of `size` non-overlapping ranges each.
+pub fn input(seed: u64, size: usize) -> String {
+    let mut rng = generate::rng(seed);
+    let seeds = (0..size)
+        .flat_map(|_| {
+            let start = rng.u64(..LIMIT / 2);
+            [start, rng.u64(1..LIMIT / (2 * size as u64).max(2))]
+        })
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>();
+    let maps = ENTITIES
+        .windows(2)
+        .map(|pair| format!("{}-to-{} map:\n{}", pair[0], pair[1], map(&mut rng, size)))
+        .collect::<Vec<_>>();
+    format!("seeds: {}\n\n{}", seeds.join(" "), maps.join("\n\n"))
+}
+
+// `size` mappings as `destination source length`, sources cut out of [0, LIMIT)
+fn map(rng: &mut Rng, size: usize) -> String {
+    let mut starts = (0..size).map(|_| rng.u64(..LIMIT)).collect::<Vec<_>>();
+    starts.sort_unstable();
+    starts.dedup();
+    let mut mappings = starts
+        .iter()
+        .zip(starts.iter().skip(1).chain([&LIMIT]))
+        .map(|(&start, &next)| {
+            let length = rng.u64(1..=next - start);
+            let destination = rng.u64(..LIMIT - length);
+            format!("{destination} {start} {length}")
+        })
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut mappings);
+    mappings.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    // the lowest locations of the seeds, then of the seed ranges, splitting the ranges
+    // against each mapping of each map in turn
+    fn reference(input: &str) -> (u64, u64) {
+        let mut blocks = input.split("\n\n");
+        let seeds = blocks.next().unwrap()["seeds: ".len()..]
+            .split(' ')
+            .map(|value| value.parse().unwrap())
+            .collect::<Vec<u64>>();
+        let maps = blocks
+            .map(|block| {
+                block
+                    .lines()
+                    .skip(1)
+                    .map(|line| {
+                        let values = line
+                            .split(' ')
+                            .map(|value| value.parse().unwrap())
+                            .collect::<Vec<u64>>();
+                        (values[0], values[1], values[2])
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // half-open ranges, the seeds of part one being ranges of one
+        let lowest = |mut ranges: Vec<(u64, u64)>| {
+            for map in &maps {
+                let mut mapped = Vec::new();
+                for &(destination, source, length) in map {
+                    let mut unmapped = Vec::new();
+                    for (start, end) in ranges {
+                        let (from, to) = (start.max(source), end.min(source + length));
+                        if from < to {
+                            mapped.push((from - source + destination, to - source + destination));
+                        }
+                        if start < from.min(end) {
+                            unmapped.push((start, from.min(end)));
+                        }
+                        if to.max(start) < end {
+                            unmapped.push((to.max(start), end));
+                        }
+                    }
+                    ranges = unmapped;
+                }
+                ranges.extend(mapped);
+            }
+            ranges.iter().map(|range| range.0).min().unwrap()
+        };
+        let part1 = lowest(seeds.iter().map(|&seed| (seed, seed + 1)).collect());
+        let part2 = lowest(
+            seeds
+                .chunks(2)
+                .map(|pair| (pair[0], pair[0] + pair[1]))
+                .collect(),
+        );
+        (part1, part2)
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(10)]
+    #[case(100)]
+    fn test_solves_generated_input(#[case] size: usize) -> miette::Result<()> {
+        let input = input(generate::SEED, size);
+        let (part1, part2) = reference(&input);
+        assert_eq!(part1.to_string(), crate::part1::process(&input)?);
+        assert_eq!(part2.to_string(), crate::part2::process(&input)?);
+        Ok(())
+    }
+}
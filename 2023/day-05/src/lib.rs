@@ -1,4 +1,11 @@
-pub mod custom_error;
-
+pub mod almanac;
+pub mod generate;
 pub mod part1;
 pub mod part2;
+
+aoc_common::solutions! {
+    year: 2023,
+    day: 5,
+    1 => part1::process,
+    2 => part2::process,
+}
@@ -0,0 +1,6 @@
+aoc_common::check_heap!(day_05, size: 100, {
+    // 163_320 bytes at the peak, 4_453 allocations when measured
+    part1: { peak_bytes: 210_000, total_blocks: 5_600 },
+    // 164_920 bytes at the peak, 4_558 allocations when measured
+    part2: { peak_bytes: 210_000, total_blocks: 5_700 },
+});
@@ -0,0 +1,69 @@
+use aoc_common::generate;
+
+/// `size` races of two-digit durations against beatable records of up to three digits.
+///
+/// Part one multiplies the ways to win each race and part two concatenates the races,
+/// their numbers only hold up to 4 of them, as many as in the puzzle.
+pub fn input(seed: u64, size: usize) -> String {
+    let mut rng = generate::rng(seed);
+    let races = (0..size)
+        .map(|_| {
+            let time = rng.u32(10..100);
+            (time, rng.u32(1..(time * time / 4).min(1000)))
+        })
+        .collect::<Vec<_>>();
+    let row = |name: &str, values: Vec<u32>| {
+        let values = values
+            .iter()
+            .map(|value| format!("{value:>4}"))
+            .collect::<String>();
+        format!("{name:<9}{values}")
+    };
+    format!(
+        "{}\n{}",
+        row("Time:", races.iter().map(|race| race.0).collect()),
+        row("Distance:", races.iter().map(|race| race.1).collect())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    // the ways to beat each race, trying every hold time
+    fn reference(input: &str) -> (u64, u64) {
+        let ways = |time: u64, distance: u64| {
+            (0..=time)
+                .filter(|hold| hold * (time - hold) > distance)
+                .count() as u64
+        };
+        let rows = input
+            .lines()
+            .map(|line| line.split_whitespace().skip(1).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let part1 = rows[0]
+            .iter()
+            .zip(&rows[1])
+            .map(|(time, distance)| ways(time.parse().unwrap(), distance.parse().unwrap()))
+            .product();
+        let part2 = ways(
+            rows[0].concat().parse().unwrap(),
+            rows[1].concat().parse().unwrap(),
+        );
+        (part1, part2)
+    }
+
+    // four races make a race of too many hold times to try them all
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    #[case(3)]
+    fn test_solves_generated_input(#[case] size: usize) -> miette::Result<()> {
+        let input = input(generate::SEED, size);
+        let (part1, part2) = reference(&input);
+        assert_eq!(part1.to_string(), crate::part1::process(&input)?);
+        assert_eq!(part2.to_string(), crate::part2::process(&input)?);
+        Ok(())
+    }
+}
@@ -1,4 +1,10 @@
-pub mod custom_error;
-
+pub mod generate;
 pub mod part1;
 pub mod part2;
+
+aoc_common::solutions! {
+    year: 2023,
+    day: 6,
+    1 => part1::process,
+    2 => part2::process,
+}
@@ -6,7 +6,7 @@ use nom::{
 };
 use nom_supreme::{parser_ext::ParserExt, tag::complete::tag};
 
-use crate::custom_error::AocError;
+use aoc_common::{trace::detail, AocError};
 
 fn travel_distance(hold: u32, duration: u32) -> u32 {
     // each hold time unit gives one unit of speed for the remaining duration
@@ -38,11 +38,11 @@ impl Run {
 }
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     runs: Vec<Run>,
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 fn parse_game(input: &str) -> IResult<&str, Game> {
     let (input, times): (&str, Vec<u32>) = separated_list1(space1, nom::character::complete::u32)
         .preceded_by(tag("Time:").precedes(space1))
@@ -60,9 +60,21 @@ fn parse_game(input: &str) -> IResult<&str, Game> {
     Ok((input, Game { runs }))
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, game) = parse_game(input).map_err(|e| AocError::ParseError(e.to_string()))?;
+    solve(parse(input)?)
+}
+
+/// Read the time and record distance of each race.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<Game, AocError> {
+    let (_, game) = parse_game(input).map_err(|e| AocError::from_nom(input, e))?;
+    Ok(game)
+}
+
+/// Multiply the numbers of ways to beat each record.
+#[tracing::instrument(skip_all)]
+pub fn solve(game: Game) -> miette::Result<String, AocError> {
     let result: u32 = game.runs.iter().map(|run| run.ways_to_beat()).product();
     Ok(result.to_string())
 }
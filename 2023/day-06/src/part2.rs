@@ -5,7 +5,7 @@ use nom::{
 };
 use nom_supreme::{parser_ext::ParserExt, tag::complete::tag};
 
-use crate::custom_error::AocError;
+use aoc_common::{trace::detail, AocError};
 
 #[derive(Debug)]
 struct Run {
@@ -44,7 +44,7 @@ impl Run {
 }
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     run: Run,
 }
 
@@ -67,7 +67,7 @@ fn concatenate_int(ts: Vec<u64>) -> u64 {
         .0
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 fn parse_game(input: &str) -> IResult<&str, Game> {
     let (input, time) = separated_list1(space1, nom::character::complete::u64)
         .preceded_by(tag("Time:").precedes(space1))
@@ -83,9 +83,21 @@ fn parse_game(input: &str) -> IResult<&str, Game> {
     Ok((input, Game { run }))
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, game) = parse_game(input).map_err(|e| AocError::ParseError(e.to_string()))?;
+    solve(parse(input)?)
+}
+
+/// Read the time and record distance of the single race, ignoring the spaces.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<Game, AocError> {
+    let (_, game) = parse_game(input).map_err(|e| AocError::from_nom(input, e))?;
+    Ok(game)
+}
+
+/// Count the ways to beat the record.
+#[tracing::instrument(skip_all)]
+pub fn solve(game: Game) -> miette::Result<String, AocError> {
     let result: u64 = game
         .run
         .ways_to_beat()
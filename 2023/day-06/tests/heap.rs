@@ -0,0 +1,6 @@
+aoc_common::check_heap!(day_06, size: 4, {
+    // 64 bytes at the peak, 4 allocations when measured
+    part1: { peak_bytes: 80, total_blocks: 5 },
+    // 32 bytes at the peak, 3 allocations when measured
+    part2: { peak_bytes: 40, total_blocks: 4 },
+});
@@ -0,0 +1,90 @@
+use std::collections::HashSet;
+
+use aoc_common::generate;
+
+/// `size` distinct hands of five cards, as in the puzzle, each with a bid of up to 1000.
+///
+/// The winnings are summed as `u32`, which holds them up to about 2000 hands.
+pub fn input(seed: u64, size: usize) -> String {
+    let mut rng = generate::rng(seed);
+    let mut hands = HashSet::with_capacity(size);
+    let mut lines = Vec::with_capacity(size);
+    while lines.len() < size {
+        let hand = generate::word(&mut rng, b"23456789TJQKA", 5);
+        if hands.insert(hand.clone()) {
+            lines.push(format!("{hand} {}", rng.u32(1..=1000)));
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    // how hands are ranked: by their type, then by the strength of each card
+    type Key = (Vec<usize>, Vec<usize>);
+
+    // the type of a hand, from the counts of its cards: the larger, the stronger
+    fn kind(hand: &[u8]) -> Vec<usize> {
+        let mut counts = hand
+            .iter()
+            .map(|card| hand.iter().filter(|other| *other == card).count())
+            .collect::<Vec<_>>();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        counts
+    }
+
+    // the winnings of both parts, trying every card in place of the jokers of part two
+    fn reference(input: &str) -> (u64, u64) {
+        let hands = input
+            .lines()
+            .map(|line| {
+                let (hand, bid) = line.split_once(' ').unwrap();
+                (hand.as_bytes(), bid.parse::<u64>().unwrap())
+            })
+            .collect::<Vec<_>>();
+        let winnings = |key: &dyn Fn(&[u8]) -> Key| {
+            let mut ranked = hands
+                .iter()
+                .map(|(hand, bid)| (key(hand), *bid))
+                .collect::<Vec<_>>();
+            ranked.sort();
+            (1..).zip(ranked).map(|(rank, (_, bid))| rank * bid).sum()
+        };
+        let strength = |order: &[u8], hand: &[u8]| {
+            hand.iter()
+                .map(|card| order.iter().position(|c| c == card).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let part1 = winnings(&|hand| (kind(hand), strength(b"23456789TJQKA", hand)));
+        let part2 = winnings(&|hand| {
+            let best = b"23456789TQKA"
+                .iter()
+                .map(|joker| {
+                    let hand = hand
+                        .iter()
+                        .map(|card| if *card == b'J' { *joker } else { *card })
+                        .collect::<Vec<_>>();
+                    kind(&hand)
+                })
+                .max()
+                .unwrap();
+            (best, strength(b"J23456789TQKA", hand))
+        });
+        (part1, part2)
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(100)]
+    #[case(1000)]
+    fn test_solves_generated_input(#[case] size: usize) -> miette::Result<()> {
+        let input = input(generate::SEED, size);
+        let (part1, part2) = reference(&input);
+        assert_eq!(part1.to_string(), crate::part1::process(&input)?);
+        assert_eq!(part2.to_string(), crate::part2::process(&input)?);
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    cmp::Ordering,
+    cmp::{Ordering, Reverse},
     collections::{BTreeMap, HashSet},
     fmt::{self, Display, Formatter},
 };
@@ -14,7 +14,7 @@ use nom::{
     Err, IResult,
 };
 
-use crate::custom_error::AocError;
+use aoc_common::{trace::detail, AocError};
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 enum Card {
@@ -145,7 +145,7 @@ fn get_type_simple(cards: &[Card; 5]) -> Result<HandType, Err<String>> {
     }
     // collect the number of cards of each rank and sort by count
     let mut counts = counts.into_iter().collect::<Vec<_>>();
-    counts.sort_by(|a, b| b.1.cmp(&a.1));
+    counts.sort_by_key(|(_, count)| Reverse(*count));
     let mut idx = 0;
     let mut k = Kind::One;
     let mut pattern = [Kind::One; 5];
@@ -242,7 +242,7 @@ fn parse_hand(input: &str) -> IResult<&str, Hand> {
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct Game {
+pub struct Game {
     hands: Vec<(Hand, u32)>,
 }
 
@@ -250,7 +250,7 @@ impl Game {
     fn calculate_winnings(&self) -> u64 {
         // sort the hands
         let mut hands = self.hands.clone();
-        hands.sort_by(|(a, _), (b, _)| a.cmp(b));
+        hands.sort_by_key(|(hand, _)| *hand);
         hands
             .iter()
             .enumerate()
@@ -270,9 +270,21 @@ fn parse_game(input: &str) -> IResult<&str, Game> {
     Ok((input, Game { hands }))
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, game) = parse_game(input).map_err(|e| AocError::ParseError(e.to_string()))?;
+    solve(parse(input)?)
+}
+
+/// Read the hand and bid of each player, `J` being a joker.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<Game, AocError> {
+    let (_, game) = parse_game(input).map_err(|e| AocError::from_nom(input, e))?;
+    Ok(game)
+}
+
+/// Sum the bids weighted by the rank of their hands.
+#[tracing::instrument(skip_all)]
+pub fn solve(game: Game) -> miette::Result<String, AocError> {
     let winnings = game.calculate_winnings();
     Ok(winnings.to_string())
 }
@@ -0,0 +1,6 @@
+aoc_common::check_heap!(day_07, size: 100, {
+    // 3_711 bytes at the peak, 2_928 allocations when measured
+    part1: { peak_bytes: 4_700, total_blocks: 3_700 },
+    // 3_648 bytes at the peak, 684 allocations when measured
+    part2: { peak_bytes: 4_600, total_blocks: 860 },
+});
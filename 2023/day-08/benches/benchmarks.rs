@@ -1,22 +1,153 @@
-use day_08::*;
-
 fn main() {
-    // Run registered benchmarks.
+    // Run registered benchmarks, each part timed as parse, solve and total on the puzzle
+    // input, and on generated inputs of growing sizes, then the walks of the network stepping
+    // along its map against those of its compiled table.
+    #[cfg(feature = "tracy")]
+    aoc_common::trace::init(None);
     divan::main();
 }
 
-#[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!(
-        "../input1.txt",
-    )))
-    .unwrap();
+mod part1 {
+    use aoc_common::{generate::SEED, input::day_input};
+    use day_08::{generate, part1};
+
+    #[divan::bench]
+    fn parse(bencher: divan::Bencher) {
+        let input = day_input(env!("CARGO_MANIFEST_DIR"), 1).unwrap();
+        bencher.bench(|| part1::parse(divan::black_box(&input)).unwrap());
+    }
+
+    #[divan::bench]
+    fn solve(bencher: divan::Bencher) {
+        let input = day_input(env!("CARGO_MANIFEST_DIR"), 1).unwrap();
+        bencher
+            .with_inputs(|| part1::parse(&input).unwrap())
+            .bench_values(|parsed| part1::solve(parsed).unwrap());
+    }
+
+    #[divan::bench]
+    fn total(bencher: divan::Bencher) {
+        let input = day_input(env!("CARGO_MANIFEST_DIR"), 1).unwrap();
+        bencher.bench(|| part1::process(divan::black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(consts = [100, 1_000, 10_000])]
+    fn scaling<const SIZE: usize>(bencher: divan::Bencher) {
+        let input = generate::input(SEED, SIZE);
+        bencher.bench(|| part1::process(divan::black_box(&input)).unwrap());
+    }
+}
+
+mod part2 {
+    use aoc_common::{generate::SEED, input::day_input};
+    use day_08::{generate, part2};
+
+    #[divan::bench]
+    fn parse(bencher: divan::Bencher) {
+        let input = day_input(env!("CARGO_MANIFEST_DIR"), 2).unwrap();
+        bencher.bench(|| part2::parse(divan::black_box(&input)).unwrap());
+    }
+
+    #[divan::bench]
+    fn solve(bencher: divan::Bencher) {
+        let input = day_input(env!("CARGO_MANIFEST_DIR"), 2).unwrap();
+        bencher
+            .with_inputs(|| part2::parse(&input).unwrap())
+            .bench_values(|parsed| part2::solve(parsed).unwrap());
+    }
+
+    #[divan::bench]
+    fn total(bencher: divan::Bencher) {
+        let input = day_input(env!("CARGO_MANIFEST_DIR"), 2).unwrap();
+        bencher.bench(|| part2::process(divan::black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(consts = [100, 1_000, 10_000])]
+    fn scaling<const SIZE: usize>(bencher: divan::Bencher) {
+        let input = generate::input(SEED, SIZE);
+        bencher.bench(|| part2::process(divan::black_box(&input)).unwrap());
+    }
 }
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input2.txt",
-    )))
-    .unwrap();
-}
\ No newline at end of file
+mod walk {
+    use std::collections::BTreeMap;
+
+    use aoc_common::input::day_input;
+    use day_08::{
+        network::{ClosureVisitor, Flow, Instruction, Network, State},
+        table::Table,
+    };
+
+    fn is_end(tag: &str) -> bool {
+        tag.ends_with('Z')
+    }
+
+    #[divan::bench]
+    fn compile(bencher: divan::Bencher) {
+        let input = day_input(env!("CARGO_MANIFEST_DIR"), 2).unwrap();
+        let network = Network::parse(&input).unwrap();
+        bencher.bench(|| Table::compile(divan::black_box(&network), is_end).unwrap());
+    }
+
+    // the lookups of a walk along the map of the nodes, which only the puzzle input ending
+    // keeps from going round forever
+    #[divan::bench]
+    fn map_to_end(bencher: divan::Bencher) {
+        let input = day_input(env!("CARGO_MANIFEST_DIR"), 1).unwrap();
+        let network = Network::parse(&input).unwrap();
+        let nodes = network
+            .nodes()
+            .map(|node| (node.tag, node))
+            .collect::<BTreeMap<_, _>>();
+        bencher.bench(|| {
+            let mut node = nodes["AAA"];
+            let mut steps = 0u64;
+            for instruction in network.instructions().iter().cycle() {
+                if node.tag == "ZZZ" {
+                    break;
+                }
+                node = nodes[match instruction {
+                    Instruction::Left => node.left,
+                    Instruction::Right => node.right,
+                }];
+                steps += 1;
+            }
+            steps
+        });
+    }
+
+    #[divan::bench]
+    fn table_to_end(bencher: divan::Bencher) {
+        let input = day_input(env!("CARGO_MANIFEST_DIR"), 1).unwrap();
+        let network = Network::parse(&input).unwrap();
+        let table = Table::compile(&network, |tag| tag == "ZZZ").unwrap();
+        let start = table.id("AAA").unwrap();
+        bencher.bench(|| {
+            let mut visitor = ClosureVisitor {
+                closure: |_: &State| Flow::Continue,
+            };
+            table
+                .walk(divan::black_box(start), &mut visitor)
+                .unwrap()
+                .step
+        });
+    }
+
+    #[divan::bench]
+    fn table_cycles(bencher: divan::Bencher) {
+        let input = day_input(env!("CARGO_MANIFEST_DIR"), 2).unwrap();
+        let network = Network::parse(&input).unwrap();
+        let table = Table::compile(&network, is_end).unwrap();
+        let starts = table
+            .tags()
+            .filter(|tag| tag.ends_with('A'))
+            .map(|start| table.id(start).unwrap())
+            .collect::<Vec<_>>();
+        bencher.bench(|| {
+            divan::black_box(&starts)
+                .iter()
+                .map(|start| table.cycle(*start))
+                .collect::<Vec<_>>()
+        });
+    }
+}
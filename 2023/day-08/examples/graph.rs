@@ -0,0 +1,37 @@
+//! Print the network of the puzzle input as a DOT graph, and what stands in the way of its walks:
+//! `cargo run -q -p day-08 --example graph | dot -Tsvg > network.svg`
+
+use aoc_common::input::day_input;
+use day_08::{graph::Graph, network::Network, table::Table};
+
+fn main() -> miette::Result<()> {
+    let input = day_input(env!("CARGO_MANIFEST_DIR"), 2)?;
+    let network = Network::parse(&input)?;
+    let graph = Graph::new(&network)?;
+    println!("{}", graph.dot());
+
+    let starts = network
+        .tags()
+        .filter(|tag| tag.ends_with('A'))
+        .collect::<Vec<_>>();
+    let unreachable = graph.unreachable(starts.iter().copied());
+    eprintln!("{} nodes unreachable from the starts", unreachable.len());
+    let is_end = |tag: &str| tag.ends_with('Z');
+    let table = Table::compile(&network, is_end)?;
+    for start in starts {
+        let cycle = table.cycle(table.id(start)?);
+        match cycle.terminals.first() {
+            Some(step) => eprintln!("{start}: reaches an end after {step} steps"),
+            None => eprintln!("{start}: {}", graph.unending(start, is_end)),
+        }
+    }
+    let cycles = graph.components().into_iter().filter(|c| c.len() > 1);
+    for component in cycles {
+        eprintln!(
+            "cycle of {} nodes through {}",
+            component.len(),
+            component[0]
+        );
+    }
+    Ok(())
+}
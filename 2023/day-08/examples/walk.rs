@@ -0,0 +1,34 @@
+//! Print the states of the walk from a node of the puzzle input until a node ending with Z, or
+//! until a number of steps: `cargo run -q -p day-08 --example walk -- AAA 100`
+
+use aoc_common::input::day_input;
+use day_08::network::{ClosureVisitor, Flow, Network, State};
+
+fn main() -> miette::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let start = args.next().unwrap_or_else(|| "AAA".to_string());
+    let limit = args
+        .next()
+        .and_then(|limit| limit.parse().ok())
+        .unwrap_or(u64::MAX);
+
+    let input = day_input(env!("CARGO_MANIFEST_DIR"), 2)?;
+    let network = Network::parse(&input)?;
+    let mut visitor = ClosureVisitor {
+        closure: |state: &State| {
+            println!(
+                "{:>8} #{:<4} {} {}",
+                state.step, state.instruction_index, state.instruction, state.node
+            );
+            match state.step {
+                // leave the start even when it is an end
+                0 => Flow::Skip,
+                step if step >= limit => Flow::Stop,
+                _ => Flow::Continue,
+            }
+        },
+    };
+    let state = network.walk(&start, |tag| tag.ends_with('Z'), &mut visitor)?;
+    eprintln!("stopped on {} after {} steps", state.node.tag, state.step);
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use aoc_common::generate;
+
+/// A network of `size` nodes (at least `AAA` and `ZZZ`, at most 26³) where both sides of
+/// a node lead one to three nodes closer to `ZZZ`, behind up to 300 instructions.
+pub fn input(seed: u64, size: usize) -> String {
+    let mut rng = generate::rng(seed);
+    let size = size.clamp(2, 26 * 26 * 26);
+    let mut names = (0..26 * 26 * 26)
+        .map(|i: usize| {
+            [i / (26 * 26), i / 26 % 26, i % 26]
+                .iter()
+                .map(|letter| char::from(b'A' + *letter as u8))
+                .collect::<String>()
+        })
+        .filter(|name| name != "AAA" && name != "ZZZ")
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut names);
+    names.truncate(size - 2);
+    names.insert(0, "AAA".to_string());
+    names.push("ZZZ".to_string());
+
+    let last = size - 1;
+    let mut nodes = (0..size)
+        .map(|i| {
+            let left = (i + rng.usize(1..=3)).min(last);
+            let right = (i + rng.usize(1..=3)).min(last);
+            format!("{} = ({}, {})", names[i], names[left], names[right])
+        })
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut nodes);
+
+    let len = rng.usize(1..=300);
+    let instructions = generate::word(&mut rng, b"LR", len);
+    format!("{instructions}\n\n{}", nodes.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::collections::HashMap;
+
+    type Nodes<'a> = HashMap<&'a str, (&'a str, &'a str)>;
+
+    // the steps until the walks from `current` all stand on end nodes, one step at a time
+    fn steps<'a>(
+        instructions: &str,
+        nodes: &Nodes<'a>,
+        mut current: Vec<&'a str>,
+        is_end: fn(&str) -> bool,
+    ) -> u64 {
+        let mut steps = 0;
+        for instruction in instructions.bytes().cycle() {
+            if current.iter().all(|tag| is_end(tag)) {
+                break;
+            }
+            for tag in &mut current {
+                let (left, right) = nodes[*tag];
+                *tag = if instruction == b'L' { left } else { right };
+            }
+            steps += 1;
+        }
+        steps
+    }
+
+    // both parts walking one step at a time: every node leads closer to ZZZ, which leads to
+    // itself, so all the walks end there within `size` steps
+    fn reference(input: &str) -> (u64, u64) {
+        let (instructions, nodes) = input.split_once("\n\n").unwrap();
+        let nodes = nodes
+            .lines()
+            .map(|line| (&line[0..3], (&line[7..10], &line[12..15])))
+            .collect::<Nodes>();
+        let starts = nodes.keys().copied().filter(|tag| tag.ends_with('A'));
+        (
+            steps(instructions, &nodes, vec!["AAA"], |tag| tag == "ZZZ"),
+            steps(instructions, &nodes, starts.collect(), |tag| {
+                tag.ends_with('Z')
+            }),
+        )
+    }
+
+    #[rstest]
+    #[case(2)]
+    #[case(100)]
+    #[case(10000)]
+    fn test_solves_generated_input(#[case] size: usize) -> miette::Result<()> {
+        let input = input(generate::SEED, size);
+        let (part1, part2) = reference(&input);
+        assert_eq!(part1.to_string(), crate::part1::process(&input)?);
+        assert_eq!(part2.to_string(), crate::part2::process(&input)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+
+use petgraph::{
+    algo::tarjan_scc,
+    dot::Dot,
+    graph::{DiGraph, NodeIndex},
+    visit::{Dfs, VisitMap},
+};
+
+use aoc_common::AocError;
+
+use crate::network::{Instruction, Network};
+
+/// A [`Network`] as a directed graph, each node leading to its left and right sides along an
+/// edge weighted by the instruction that takes it.
+#[derive(Debug, Clone)]
+pub struct Graph<'a> {
+    graph: DiGraph<&'a str, Instruction>,
+    indices: BTreeMap<&'a str, NodeIndex>,
+}
+
+impl<'a> Graph<'a> {
+    pub fn new(network: &Network<'a>) -> Result<Self, AocError> {
+        let mut graph = DiGraph::new();
+        let indices = network
+            .tags()
+            .map(|tag| (tag, graph.add_node(tag)))
+            .collect::<BTreeMap<_, _>>();
+        for node in network.nodes() {
+            let from = indices[node.tag];
+            for (side, to) in [
+                (Instruction::Left, node.left),
+                (Instruction::Right, node.right),
+            ] {
+                let to = *indices.get(to).ok_or_else(|| {
+                    AocError::LogicError(format!(
+                        "node [{}] leads to unknown node [{to}]",
+                        node.tag
+                    ))
+                })?;
+                graph.add_edge(from, to, side);
+            }
+        }
+        Ok(Self { graph, indices })
+    }
+
+    /// The graph in the DOT language, to render with Graphviz: `dot -Tsvg network.dot`.
+    pub fn dot(&self) -> String {
+        Dot::new(&self.graph).to_string()
+    }
+
+    /// Strongly connected components, each sorted, the ones holding several nodes or a node
+    /// leading to itself being where walks can go round.
+    pub fn components(&self) -> Vec<Vec<&'a str>> {
+        let mut components = tarjan_scc(&self.graph)
+            .into_iter()
+            .map(|component| {
+                let mut tags = component
+                    .into_iter()
+                    .map(|index| self.graph[index])
+                    .collect::<Vec<_>>();
+                tags.sort_unstable();
+                tags
+            })
+            .collect::<Vec<_>>();
+        components.sort_unstable();
+        components
+    }
+
+    /// Nodes that no walk from `starts` can reach, whatever the instructions.
+    pub fn unreachable<'s>(&self, starts: impl IntoIterator<Item = &'s str>) -> Vec<&'a str> {
+        let mut dfs = Dfs::empty(&self.graph);
+        for start in starts
+            .into_iter()
+            .filter_map(|start| self.indices.get(start))
+        {
+            dfs.move_to(*start);
+            while dfs.next(&self.graph).is_some() {}
+        }
+        self.indices
+            .iter()
+            .filter(|(_, index)| !dfs.discovered.is_visited(*index))
+            .map(|(tag, _)| *tag)
+            .collect()
+    }
+
+    /// Why the walk from `start` never stands on a node for which `is_end` holds: either none
+    /// of them can be reached from it, whatever the instructions, or the order of the
+    /// instructions keeps the walk away from those that can.
+    pub fn unending(&self, start: &str, is_end: impl Fn(&str) -> bool) -> AocError {
+        let unreachable = self.unreachable([start]);
+        let mut ends = self
+            .indices
+            .keys()
+            .filter(|tag| is_end(tag) && unreachable.binary_search(tag).is_err());
+        AocError::Unsolvable(match ends.next() {
+            None => format!("no end node can be reached from {start}, whatever the instructions"),
+            Some(end) => format!(
+                "end nodes such as {end} can be reached from {start}, but the order of the \
+                 instructions keeps the walk away from them"
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+YYY = (11A, XXX)";
+
+    fn graph(input: &str) -> miette::Result<Graph<'_>> {
+        Ok(Graph::new(&Network::parse(input)?)?)
+    }
+
+    #[test]
+    fn test_dot() -> miette::Result<()> {
+        let dot = graph(EXAMPLE)?.dot();
+        assert!(dot.starts_with("digraph {"), "{dot}");
+        assert!(dot.contains("[ label = \"11A\" ]"), "{dot}");
+        assert!(dot.contains("[ label = \"L\" ]"), "{dot}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_components() -> miette::Result<()> {
+        let components = graph(EXAMPLE)?.components();
+        let expected: Vec<Vec<&str>> = vec![
+            vec!["11A"],
+            vec!["11B", "11Z"],
+            vec!["22A"],
+            vec!["22B", "22C", "22Z"],
+            vec!["XXX"],
+            vec!["YYY"],
+        ];
+        assert_eq!(expected, components);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(vec!["11A", "22A"], vec!["YYY"])]
+    #[case(vec!["22A"], vec!["11A", "11B", "11Z", "YYY"])]
+    #[case(vec!["YYY"], vec!["22A", "22B", "22C", "22Z"])]
+    fn test_unreachable(
+        #[case] starts: Vec<&str>,
+        #[case] expected: Vec<&str>,
+    ) -> miette::Result<()> {
+        assert_eq!(expected, graph(EXAMPLE)?.unreachable(starts));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("11A", "end nodes such as 11Z can be reached from 11A")]
+    #[case("22A", "end nodes such as 22Z can be reached from 22A")]
+    #[case("XXX", "no end node can be reached from XXX")]
+    fn test_unending(#[case] start: &str, #[case] expected: &str) -> miette::Result<()> {
+        let unending = graph(EXAMPLE)?.unending(start, |tag| tag.ends_with('Z'));
+        assert!(unending.to_string().contains(expected), "{unending}");
+        Ok(())
+    }
+}
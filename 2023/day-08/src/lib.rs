@@ -1,4 +1,13 @@
-pub mod custom_error;
-
+pub mod generate;
+pub mod graph;
+pub mod network;
 pub mod part1;
 pub mod part2;
+pub mod table;
+
+aoc_common::solutions! {
+    year: 2023,
+    day: 8,
+    1 => part1::process,
+    2 => part2::process,
+}
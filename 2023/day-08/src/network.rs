@@ -0,0 +1,361 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::alphanumeric1,
+    character::complete::{self, line_ending, one_of},
+    combinator::map_res,
+    multi::{many1, separated_list1},
+    sequence::{self, delimited, terminated},
+};
+use std::{collections::BTreeMap, fmt::Display};
+
+use aoc_common::{AocError, Positioned};
+use miette::SourceSpan;
+use nom::IResult;
+
+use crate::table::Table;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Instruction {
+    Left,
+    Right,
+}
+
+impl TryFrom<char> for Instruction {
+    type Error = AocError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'L' => Ok(Instruction::Left),
+            'R' => Ok(Instruction::Right),
+            _ => Err(AocError::LogicError("invalid instruction".to_string())),
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Left => write!(f, "L"),
+            Instruction::Right => write!(f, "R"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node<'a> {
+    pub tag: &'a str,
+    pub left: &'a str,
+    pub right: &'a str,
+}
+
+impl Display for Node<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} -> ({},{})", self.tag, self.left, self.right)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network<'a> {
+    // what the network was parsed from, for the diagnostics to point into
+    input: &'a str,
+    instructions: Vec<Instruction>,
+    nodes: BTreeMap<&'a str, Node<'a>>,
+}
+
+/// Where a walk stands after `step` steps, about to follow `instruction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct State<'n> {
+    pub step: u64,
+    pub instruction_index: usize,
+    pub instruction: Instruction,
+    pub node: &'n Node<'n>,
+}
+
+/// What a walk does once a visitor saw a state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    /// Go on, stopping here if the node is an end.
+    Continue,
+    /// Stop here, whatever the node.
+    Stop,
+    /// Go on, even if the node is an end.
+    Skip,
+}
+
+pub trait Visitor {
+    fn visit(&mut self, state: &State) -> Flow;
+}
+
+pub struct ClosureVisitor<F: FnMut(&State) -> Flow> {
+    pub closure: F,
+}
+
+impl<F: FnMut(&State) -> Flow> Visitor for ClosureVisitor<F> {
+    // once per step of a walk, far too often for a span outside of debugging
+    #[cfg_attr(
+        feature = "detailed",
+        tracing::instrument(level = "trace", skip_all, fields(step = state.step))
+    )]
+    fn visit(&mut self, state: &State) -> Flow {
+        (self.closure)(state)
+    }
+}
+
+/// The states a walk goes through from its start, the state at each step being the node and
+/// the index of the next instruction: a prefix, then a cycle repeated forever.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    /// Steps before the first state of the cycle.
+    pub prefix: u64,
+    /// Steps to go round the cycle once.
+    pub length: u64,
+    /// Steps, in order, at which the walk stands on a terminal node, before it first completes
+    /// the cycle: from then on, those within the cycle repeat every `length` steps.
+    pub terminals: Vec<u64>,
+}
+
+impl Cycle {
+    /// Whether the walk stands on a terminal node after `step` steps.
+    pub fn is_terminal(&self, step: u64) -> bool {
+        let step = if step < self.prefix + self.length {
+            step
+        } else {
+            self.prefix + (step - self.prefix) % self.length
+        };
+        self.terminals.binary_search(&step).is_ok()
+    }
+}
+
+impl<'a> Network<'a> {
+    pub fn parse(input: &'a str) -> Result<Self, AocError> {
+        let (_, network) = parse_network(input).map_err(|e| AocError::from_nom(input, e))?;
+        Ok(network)
+    }
+
+    /// Tags of the nodes, in order.
+    pub fn tags(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.nodes.keys().copied()
+    }
+
+    /// Nodes, in the order of their tags.
+    pub fn nodes(&self) -> impl Iterator<Item = &Node<'a>> {
+        self.nodes.values()
+    }
+
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// Follow the instructions from `start` until a node for which `is_end` holds, or until the
+    /// visitor stops the walk, and return the state it stopped on, through a [`Table`] compiled
+    /// for this walk.
+    pub fn walk(
+        &self,
+        start: &str,
+        is_end: impl Fn(&str) -> bool,
+        visitor: &mut dyn Visitor,
+    ) -> Result<State<'_>, AocError> {
+        let table = Table::compile(self, is_end)?;
+        table.walk(table.id(start)?, visitor)
+    }
+
+    /// Follow the instructions from `start` until the walk loops, and locate the nodes for
+    /// which `is_terminal` holds along the way, through a [`Table`] compiled for this walk.
+    pub fn cycle(
+        &self,
+        start: &str,
+        is_terminal: impl Fn(&str) -> bool,
+    ) -> Result<Cycle, AocError> {
+        let table = Table::compile(self, is_terminal)?;
+        Ok(table.cycle(table.id(start)?))
+    }
+
+    pub fn input(&self) -> &'a str {
+        self.input
+    }
+}
+
+/// The line of `input` defining the node `tag`, a slice of `input`.
+pub fn definition(input: &str, tag: &str) -> SourceSpan {
+    let start = tag.offset_in(input);
+    let len = input[start..].lines().next().map_or(0, str::len);
+    (start, len).into()
+}
+
+fn parse_instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
+    let (input, instructions) = terminated(
+        many1(map_res(one_of("LR"), Instruction::try_from)),
+        line_ending,
+    )(input)?;
+    Ok((input, instructions))
+}
+
+fn parse_node(input: &str) -> IResult<&str, Node<'_>> {
+    let (input, node_tag) = alphanumeric1(input)?;
+    let (input, _) = tag(" = ")(input)?;
+    let (input, (left, right)) = delimited(
+        complete::char('('),
+        sequence::separated_pair(alphanumeric1, tag(", "), alphanumeric1),
+        complete::char(')'),
+    )(input)?;
+    Ok((
+        input,
+        Node {
+            tag: node_tag,
+            left,
+            right,
+        },
+    ))
+}
+
+fn parse_network(source: &str) -> IResult<&str, Network<'_>> {
+    let (input, instructions) = parse_instructions(source)?;
+    let (input, _) = many1(line_ending)(input)?;
+    let (input, nodes) = separated_list1(line_ending, parse_node)(input)?;
+    let nodes = nodes
+        .into_iter()
+        .map(|node| (node.tag, node))
+        .collect::<BTreeMap<&str, Node>>();
+    Ok((
+        input,
+        Network {
+            input: source,
+            instructions,
+            nodes,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn network() -> Network<'static> {
+        let instructions = vec![Instruction::Left, Instruction::Right];
+        let nodes = [
+            Node {
+                tag: "A",
+                left: "B",
+                right: "B",
+            },
+            Node {
+                tag: "B",
+                left: "Z",
+                right: "C",
+            },
+            Node {
+                tag: "C",
+                left: "A",
+                right: "Z",
+            },
+            Node {
+                tag: "Z",
+                left: "Z",
+                right: "Z",
+            },
+        ];
+        Network {
+            input: "",
+            instructions,
+            nodes: nodes
+                .iter()
+                .map(|node| (node.tag, node.clone()))
+                .collect::<BTreeMap<&str, Node>>(),
+        }
+    }
+
+    #[rstest]
+    #[case(|_: &State| Flow::Continue, "Z", vec!["A", "B", "C", "A", "B", "Z"])]
+    #[case(
+        |state: &State| if state.step == 2 { Flow::Stop } else { Flow::Continue },
+        "Z",
+        vec!["A", "B", "C"]
+    )]
+    // the start is an end, skipped
+    #[case(
+        |state: &State| if state.step == 0 { Flow::Skip } else { Flow::Continue },
+        "A",
+        vec!["A", "B", "C", "A"]
+    )]
+    fn test_walk(
+        #[case] flow: fn(&State) -> Flow,
+        #[case] end: &str,
+        #[case] expected: Vec<&str>,
+    ) -> miette::Result<()> {
+        let network = network();
+        let mut visited = Vec::<String>::new();
+        let mut visitor = ClosureVisitor {
+            closure: |state: &State| {
+                visited.push(state.node.tag.to_string());
+                flow(state)
+            },
+        };
+        let state = network.walk("A", |tag| tag == end, &mut visitor)?;
+        assert_eq!(expected.last().unwrap(), &state.node.tag);
+        assert_eq!(expected.len() as u64 - 1, state.step);
+        assert_eq!(expected, visited);
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("L\n\nA = (B, B)\nB = (A, A)", 0, vec![("A", 0), ("B", 0)])]
+    #[case("LR\n\nS = (A, A)\nA = (B, B)\nB = (A, A)", 1, vec![("A", 1), ("B", 0)])]
+    fn test_walk_loop(
+        #[case] input: &str,
+        #[case] prefix: u64,
+        #[case] cycle: Vec<(&str, usize)>,
+    ) -> miette::Result<()> {
+        let network = Network::parse(input)?;
+        let mut visitor = ClosureVisitor {
+            closure: |_: &State| Flow::Continue,
+        };
+        let start = if prefix == 0 { "A" } else { "S" };
+        match network.walk(start, |tag| tag == "Z", &mut visitor) {
+            Err(AocError::LoopDetected(e)) => {
+                assert_eq!(prefix, e.prefix);
+                assert_eq!(cycle.len() as u64, e.length);
+                let cycle = cycle
+                    .into_iter()
+                    .map(|(tag, index)| (tag.to_string(), index))
+                    .collect::<Vec<_>>();
+                assert_eq!(cycle, e.cycle);
+                // each label on the definition of a node of the cycle
+                let labeled = e
+                    .labels
+                    .iter()
+                    .map(|label| &input[label.offset()..label.offset() + label.len()])
+                    .collect::<Vec<_>>();
+                assert_eq!(vec!["A = (B, B)", "B = (A, A)"], labeled);
+            }
+            other => panic!("unexpected walk {other:?}"),
+        }
+        Ok(())
+    }
+
+    #[rstest]
+    // A B C A B Z Z...
+    #[case("A", 5, 2, vec![5, 6])]
+    // B Z Z...
+    #[case("B", 1, 2, vec![1, 2])]
+    fn test_cycle(
+        #[case] start: &str,
+        #[case] prefix: u64,
+        #[case] length: u64,
+        #[case] terminals: Vec<u64>,
+    ) -> miette::Result<()> {
+        let cycle = network().cycle(start, |tag| tag == "Z")?;
+        assert_eq!(
+            Cycle {
+                prefix,
+                length,
+                terminals
+            },
+            cycle
+        );
+        assert!(!cycle.is_terminal(prefix - 1));
+        assert!((prefix..prefix + 10).all(|step| cycle.is_terminal(step)));
+        Ok(())
+    }
+}
@@ -1,18 +1,276 @@
-use crate::custom_error::AocError;
+use aoc_common::{trace::detail, AocError};
 
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    Ok("".to_string())
+use crate::{
+    network::{Cycle, Network},
+    table::Table,
+};
+
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(parse(input)?)
+}
+
+/// Read the instructions and the nodes of the network.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<Network<'_>, AocError> {
+    Network::parse(input)
+}
+
+/// Count the steps until the walks from every node ending with `A` all stand on nodes ending
+/// with `Z` at once, from the cycles [`Table::cycle`] finds them going round, the table walking
+/// for [`Network::walk`] too.
+#[tracing::instrument(skip_all)]
+pub fn solve(network: Network<'_>) -> miette::Result<String, AocError> {
+    let table = Table::compile(&network, |tag| tag.ends_with('Z'))?;
+    let cycles = table
+        .tags()
+        .filter(|tag| tag.ends_with('A'))
+        .map(|start| {
+            let start = table.id(start)?;
+            let cycle = table.cycle(start);
+            // a walk never standing on a terminal node goes round its cycle forever
+            match cycle.terminals.is_empty() {
+                true => Err(table.loop_detected(start, &cycle)),
+                false => Ok(cycle),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if cycles.is_empty() {
+        return Err(AocError::Unsolvable("no node ends with A".to_string()));
+    }
+    let steps = first_common_terminal(&cycles)?.ok_or(AocError::Unsolvable(
+        "the walks never stand on nodes ending with Z at once".to_string(),
+    ))?;
+    Ok(steps.to_string())
+}
+
+// the first step at which every walk stands on a terminal node
+fn first_common_terminal(cycles: &[Cycle]) -> Result<Option<u64>, AocError> {
+    // until every walk is within its cycle, the steps are checked one by one
+    let settled = cycles.iter().map(|cycle| cycle.prefix).max().unwrap_or(0);
+    if let Some(step) = (0..settled).find(|&step| cycles.iter().all(|c| c.is_terminal(step))) {
+        return Ok(Some(step));
+    }
+
+    // from then on, a walk stands on a terminal node at the steps congruent to one of the
+    // terminals of its cycle, and the walks all do at the solutions of one of the systems
+    let mut congruences = vec![Congruence {
+        residue: 0,
+        modulus: 1,
+    }];
+    for cycle in cycles {
+        let mut combined = Vec::new();
+        for congruence in &congruences {
+            for terminal in cycle.terminals.iter().filter(|&&t| t >= cycle.prefix) {
+                let other = Congruence {
+                    residue: terminal % cycle.length,
+                    modulus: cycle.length,
+                };
+                combined.extend(congruence.combine(other)?);
+            }
+        }
+        combined.sort_unstable_by_key(|c| (c.modulus, c.residue));
+        combined.dedup();
+        congruences = combined;
+    }
+    let steps = congruences
+        .iter()
+        .map(|congruence| congruence.first_from(settled))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(steps.into_iter().min())
+}
+
+/// The steps `x` such that `x ≡ residue (mod modulus)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Congruence {
+    residue: u64,
+    modulus: u64,
+}
+
+impl Congruence {
+    /// The steps satisfying both congruences, none when they contradict each other (Chinese
+    /// remainder theorem, the moduli need not be coprime).
+    fn combine(self, other: Self) -> Result<Option<Self>, AocError> {
+        let (gcd, inverse) = gcd_inverse(self.modulus, other.modulus);
+        let difference = i128::from(other.residue) - i128::from(self.residue);
+        if difference % i128::from(gcd) != 0 {
+            return Ok(None);
+        }
+        let modulus = (self.modulus / gcd)
+            .checked_mul(other.modulus)
+            .ok_or(AocError::Overflow("the period of the walks".to_string()))?;
+        // x = self.residue + self.modulus * k, with k ≡ difference / gcd * inverse
+        let reduced = other.modulus / gcd;
+        let quotient = (difference / i128::from(gcd)).rem_euclid(i128::from(reduced)) as u64;
+        let k = mul_mod(quotient, inverse, reduced);
+        // taken in u128, where the offset and the residue always fit together
+        let residue = (u128::from(self.modulus) * u128::from(k) + u128::from(self.residue))
+            % u128::from(modulus);
+        let residue = residue as u64;
+        Ok(Some(Self { residue, modulus }))
+    }
+
+    /// The first step from `start` satisfying the congruence.
+    fn first_from(self, start: u64) -> Result<u64, AocError> {
+        let from = start % self.modulus;
+        let offset = if self.residue >= from {
+            self.residue - from
+        } else {
+            self.modulus - (from - self.residue)
+        };
+        start
+            .checked_add(offset)
+            .ok_or(AocError::Overflow("the steps of the walks".to_string()))
+    }
+}
+
+// a * b modulo m, the product taken in u128 where it always fits
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(m)) as u64
+}
+
+// gcd(a, b) and the inverse of a / gcd modulo b / gcd, by the extended Euclidean algorithm
+fn gcd_inverse(a: u64, b: u64) -> (u64, u64) {
+    let (mut old_r, mut r) = (i128::from(a), i128::from(b));
+    let (mut old_s, mut s) = (1i128, 0i128);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+    }
+    let reduced = i128::from(b) / old_r;
+    (old_r as u64, old_s.rem_euclid(reduced) as u64)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)",
+        "6"
+    )]
+    // the terminals are not at the end of the cycles, and not at the same offset
+    #[case(
+        "L
+
+AAA = (ABB, ABB)
+ABB = (ABZ, ABZ)
+ABZ = (ABB, ABB)
+BBA = (BBZ, BBZ)
+BBZ = (BBB, BBB)
+BBB = (BBC, BBC)
+BBC = (BBZ, BBZ)",
+        "4"
+    )]
+    // a terminal only seen before the cycle
+    #[case(
+        "L
+
+AAA = (AAZ, AAZ)
+AAZ = (XXX, XXX)
+XXX = (XXX, XXX)",
+        "1"
+    )]
+    fn test_process(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(input)?);
+        Ok(())
+    }
 
     #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "";
-        assert_eq!("", process(input)?);
+    fn test_process_unsolvable() {
+        // the walks are on terminals at odd and even steps
+        let input = "L
+
+AAA = (AAZ, AAZ)
+AAZ = (AAA, AAA)
+BBA = (BBB, BBB)
+BBB = (BBZ, BBZ)
+BBZ = (BBB, BBB)";
+        assert!(matches!(process(input), Err(AocError::Unsolvable(_))));
+    }
+
+    #[test]
+    fn test_process_loop() {
+        // the walk from BBA goes round BBB and BBC, never on a node ending with Z
+        let input = "L
+
+AAA = (AAZ, AAZ)
+AAZ = (AAZ, AAZ)
+BBA = (BBB, BBB)
+BBB = (BBC, BBC)
+BBC = (BBB, BBB)";
+        match process(input) {
+            Err(AocError::LoopDetected(e)) => {
+                assert_eq!(1, e.prefix);
+                let cycle = vec![("BBB".to_string(), 0), ("BBC".to_string(), 0)];
+                assert_eq!(cycle, e.cycle);
+                let related = e
+                    .related
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<String>();
+                assert!(
+                    related.contains("no end node can be reached from BBA"),
+                    "{related}"
+                );
+            }
+            other => panic!("unexpected answer {other:?}"),
+        }
+    }
+
+    #[rstest]
+    #[case((2, 3), (3, 5), Some((8, 15)))]
+    #[case((1, 4), (3, 6), Some((9, 12)))]
+    #[case((1, 4), (2, 6), None)]
+    #[case((0, 1), (5, 7), Some((5, 7)))]
+    // coprime moduli just below 2^32, the period just below 2^64
+    #[case(
+        (123_456_789, 4_294_967_291),
+        (987_654_321, 4_294_967_279),
+        Some((309_308_344_532_033_940, 18_446_743_979_220_271_189))
+    )]
+    // a residue past its modulus, the period just below 2^64
+    #[case(
+        (18_446_744_073_709_551_605, 3),
+        (0, 6_148_914_691_236_517_202),
+        Some((6_148_914_691_236_517_202, 18_446_744_073_709_551_606))
+    )]
+    fn test_combine(
+        #[case] first: (u64, u64),
+        #[case] second: (u64, u64),
+        #[case] expected: Option<(u64, u64)>,
+    ) -> miette::Result<()> {
+        let congruence = |(residue, modulus)| Congruence { residue, modulus };
+        assert_eq!(
+            expected.map(congruence),
+            congruence(first).combine(congruence(second))?
+        );
         Ok(())
     }
+
+    #[test]
+    fn test_combine_overflow() {
+        // coprime moduli around 2^32, the period above 2^64
+        let first = Congruence {
+            residue: 1,
+            modulus: 4_294_967_291,
+        };
+        let second = Congruence {
+            residue: 2,
+            modulus: 4_294_967_311,
+        };
+        assert!(matches!(first.combine(second), Err(AocError::Overflow(_))));
+    }
 }
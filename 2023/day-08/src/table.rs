@@ -0,0 +1,328 @@
+use aoc_common::AocError;
+
+use crate::{
+    graph::Graph,
+    network::{definition, Cycle, Flow, Instruction, Network, Node, State, Visitor},
+};
+
+/// Index of a node in a [`Table`], the ids following the order of the tags.
+pub type NodeId = u16;
+
+/// A [`Network`] compiled for long walks: nodes are interned as ids into dense tables, which
+/// also hold where a full pass of the instructions leads from each node, so that a walk jumps
+/// over whole passes that do not stand on an end node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table<'n> {
+    network: &'n Network<'n>,
+    nodes: Vec<&'n Node<'n>>,
+    instructions: &'n [Instruction],
+    // the left and right sides of each node
+    next: Vec<[NodeId; 2]>,
+    is_end: Vec<bool>,
+    // the node a full pass of the instructions leads to from each node
+    jump: Vec<NodeId>,
+    // the steps of the pass from node `i` standing on an end node are `ends[ends_at[i]..ends_at[i + 1]]`
+    ends_at: Vec<u32>,
+    ends: Vec<u32>,
+}
+
+impl<'n> Table<'n> {
+    /// Intern the nodes of `network`, the walks stopping on the nodes for which `is_end` holds.
+    #[tracing::instrument(skip_all)]
+    pub fn compile(
+        network: &'n Network<'n>,
+        is_end: impl Fn(&str) -> bool,
+    ) -> Result<Self, AocError> {
+        let nodes = network.nodes().collect::<Vec<_>>();
+        if nodes.len() > usize::from(NodeId::MAX) + 1 {
+            return Err(AocError::Overflow("the ids of the nodes".to_string()));
+        }
+        let next = nodes
+            .iter()
+            .map(|node| Ok([find(&nodes, node.left)?, find(&nodes, node.right)?]))
+            .collect::<Result<Vec<_>, AocError>>()?;
+        let is_end = nodes
+            .iter()
+            .map(|node| is_end(node.tag))
+            .collect::<Vec<_>>();
+        let instructions = network.instructions();
+
+        // a full pass of the instructions from each node
+        let mut jump = Vec::with_capacity(nodes.len());
+        let mut ends_at = Vec::with_capacity(nodes.len() + 1);
+        let mut ends = Vec::new();
+        ends_at.push(0);
+        for start in 0..nodes.len() {
+            let mut current = start as NodeId;
+            for (step, instruction) in instructions.iter().enumerate() {
+                if is_end[usize::from(current)] {
+                    ends.push(step as u32);
+                }
+                current = next[usize::from(current)][side(*instruction)];
+            }
+            jump.push(current);
+            ends_at.push(ends.len() as u32);
+        }
+        Ok(Self {
+            network,
+            nodes,
+            instructions,
+            next,
+            is_end,
+            jump,
+            ends_at,
+            ends,
+        })
+    }
+
+    pub fn id(&self, tag: &str) -> Result<NodeId, AocError> {
+        find(&self.nodes, tag)
+    }
+
+    /// Tags of the nodes, in the order of their ids.
+    pub fn tags(&self) -> impl Iterator<Item = &'n str> + '_ {
+        self.nodes.iter().map(|node| node.tag)
+    }
+
+    /// The states of the walk from `start`, lazily and forever.
+    pub fn states(&self, start: NodeId) -> impl Iterator<Item = State<'n>> + '_ {
+        self.steps(start).map(|(_, state)| state)
+    }
+
+    /// Follow the instructions from `start` until an end node, or until the visitor stops the
+    /// walk, and return the state it stopped on.
+    #[tracing::instrument(skip(self, visitor))]
+    pub fn walk(&self, start: NodeId, visitor: &mut dyn Visitor) -> Result<State<'n>, AocError> {
+        // past its prefix and a round of its cycle, the walk only goes through the same states again
+        let cycle = self.cycle(start);
+        for (id, state) in self
+            .steps(start)
+            .take((cycle.prefix + cycle.length) as usize)
+        {
+            match visitor.visit(&state) {
+                Flow::Continue if self.is_end[usize::from(id)] => return Ok(state),
+                Flow::Stop => return Ok(state),
+                Flow::Continue | Flow::Skip => {}
+            }
+        }
+        Err(self.loop_detected(start, &cycle))
+    }
+
+    /// Follow the instructions from `start` until the walk loops, a pass at a time, and locate the
+    /// steps standing on an end node along the way.
+    #[tracing::instrument(skip(self))]
+    pub fn cycle(&self, start: NodeId) -> Cycle {
+        let num_instructions = self.instructions.len() as u64;
+        // the node at the start of each pass, until one comes back
+        let mut passes = Vec::new();
+        let mut pass_of = vec![None; self.nodes.len()];
+        let mut current = start;
+        let entry = loop {
+            if let Some(pass) = pass_of[usize::from(current)] {
+                break pass;
+            }
+            pass_of[usize::from(current)] = Some(passes.len());
+            passes.push(current);
+            current = self.jump[usize::from(current)];
+        };
+        let length = (passes.len() - entry) as u64 * num_instructions;
+
+        // the cycle is entered during the pass before the first one that comes back, from the
+        // first step at which that pass and its repetition stand on the same node
+        let prefix = match entry.checked_sub(1) {
+            None => 0,
+            Some(before) => {
+                let (mut first, mut repeated) = (passes[before], passes[passes.len() - 1]);
+                let mut offset = 0;
+                for instruction in self.instructions {
+                    if first == repeated {
+                        break;
+                    }
+                    first = self.next[usize::from(first)][side(*instruction)];
+                    repeated = self.next[usize::from(repeated)][side(*instruction)];
+                    offset += 1;
+                }
+                before as u64 * num_instructions + offset
+            }
+        };
+
+        let terminals = passes
+            .iter()
+            .enumerate()
+            .flat_map(|(pass, node)| {
+                let start = pass as u64 * num_instructions;
+                self.ends(*node)
+                    .iter()
+                    .map(move |offset| start + u64::from(*offset))
+            })
+            .take_while(|step| *step < prefix + length)
+            .collect();
+        Cycle {
+            prefix,
+            length,
+            terminals,
+        }
+    }
+
+    /// The error of the walk from `start` going round `cycle`, which never stands on an end node,
+    /// labeled on the definitions of its nodes and related to what the graph of the network
+    /// tells of it.
+    pub fn loop_detected(&self, start: NodeId, cycle: &Cycle) -> AocError {
+        let nodes = self
+            .states(start)
+            .skip(cycle.prefix as usize)
+            .take(cycle.length as usize)
+            .map(|state| {
+                let tag = state.node.tag;
+                let span = definition(self.network.input(), tag);
+                (tag.to_string(), state.instruction_index, span)
+            });
+        let error = AocError::loop_detected(self.network.input(), cycle.prefix, nodes);
+        let is_end = |tag: &str| self.id(tag).is_ok_and(|id| self.is_end[usize::from(id)]);
+        let related = match Graph::new(self.network) {
+            Ok(graph) => graph.unending(self.nodes[usize::from(start)].tag, is_end),
+            Err(e) => e,
+        };
+        error.with_related(related)
+    }
+
+    // the ids of the nodes the walk from `start` stands on, along with its states
+    fn steps(&self, start: NodeId) -> impl Iterator<Item = (NodeId, State<'n>)> + '_ {
+        let num_instructions = self.instructions.len() as u64;
+        (0..).scan(start, move |current, step| {
+            let id = *current;
+            let instruction_index = (step % num_instructions) as usize;
+            let instruction = self.instructions[instruction_index];
+            *current = self.next[usize::from(id)][side(instruction)];
+            Some((
+                id,
+                State {
+                    step,
+                    instruction_index,
+                    instruction,
+                    node: self.nodes[usize::from(id)],
+                },
+            ))
+        })
+    }
+
+    // the steps of the pass from `node` standing on an end node
+    fn ends(&self, node: NodeId) -> &[u32] {
+        let node = usize::from(node);
+        &self.ends[self.ends_at[node] as usize..self.ends_at[node + 1] as usize]
+    }
+}
+
+// the id of `tag` among the nodes sorted by tag
+fn find(nodes: &[&Node], tag: &str) -> Result<NodeId, AocError> {
+    nodes
+        .binary_search_by_key(&tag, |node| node.tag)
+        .map(|id| id as NodeId)
+        .map_err(|_| AocError::LogicError(format!("node [{tag}] not found")))
+}
+
+fn side(instruction: Instruction) -> usize {
+    match instruction {
+        Instruction::Left => 0,
+        Instruction::Right => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate, network::ClosureVisitor};
+    use aoc_common::generate::SEED;
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    #[test]
+    fn test_states() -> miette::Result<()> {
+        let network = Network::parse(EXAMPLE)?;
+        let table = Table::compile(&network, |tag| tag.ends_with('Z'))?;
+        let states = table
+            .states(table.id("11A")?)
+            .take(5)
+            .map(|s| (s.step, s.instruction_index, s.instruction, s.node.tag))
+            .collect::<Vec<_>>();
+        let expected = vec![
+            (0, 0, Instruction::Left, "11A"),
+            (1, 1, Instruction::Right, "11B"),
+            (2, 0, Instruction::Left, "11Z"),
+            (3, 1, Instruction::Right, "11B"),
+            (4, 0, Instruction::Left, "11Z"),
+        ];
+        assert_eq!(expected, states);
+        assert!(table.id("11Y").is_err());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("11A", 2)]
+    #[case("22A", 3)]
+    #[case("22B", 2)]
+    fn test_walk(#[case] start: &str, #[case] expected: u64) -> miette::Result<()> {
+        let network = Network::parse(EXAMPLE)?;
+        let table = Table::compile(&network, |tag| tag.ends_with('Z'))?;
+        let mut visitor = ClosureVisitor {
+            closure: |_: &State| Flow::Continue,
+        };
+        assert_eq!(expected, table.walk(table.id(start)?, &mut visitor)?.step);
+        Ok(())
+    }
+
+    #[test]
+    fn test_walk_loop() -> miette::Result<()> {
+        let network = Network::parse(EXAMPLE)?;
+        let table = Table::compile(&network, |tag| tag == "22Z")?;
+        let mut visitor = ClosureVisitor {
+            closure: |_: &State| Flow::Continue,
+        };
+        match table.walk(table.id("11A")?, &mut visitor) {
+            Err(AocError::LoopDetected(e)) => {
+                assert_eq!(1, e.prefix);
+                assert_eq!(2, e.length);
+                let cycle = vec![("11B".to_string(), 1), ("11Z".to_string(), 0)];
+                assert_eq!(cycle, e.cycle);
+            }
+            other => panic!("unexpected walk {other:?}"),
+        }
+        Ok(())
+    }
+
+    // walking one step at a time stops where the passes of the cycle first stand on an end
+    #[rstest]
+    #[case(EXAMPLE.to_string())]
+    #[case(generate::input(SEED, 100))]
+    #[case(generate::input(SEED, 1000))]
+    fn test_walk_cycle(#[case] input: String) -> miette::Result<()> {
+        let network = Network::parse(&input)?;
+        let table = Table::compile(&network, |tag| tag.ends_with('Z'))?;
+        for start in network.tags().filter(|tag| tag.ends_with('A')) {
+            let start = table.id(start)?;
+            let mut visitor = ClosureVisitor {
+                closure: |_: &State| Flow::Continue,
+            };
+            let walked = table.walk(start, &mut visitor).ok().map(|s| s.step);
+            let cycle = table.cycle(start);
+            assert_eq!(cycle.terminals.first().copied(), walked);
+            // the states the cycle found the walk coming back to
+            let mut states = table.states(start);
+            let first = states.nth(cycle.prefix as usize).unwrap();
+            let repeated = states.nth(cycle.length as usize - 1).unwrap();
+            assert_eq!(first.node, repeated.node);
+            assert_eq!(first.instruction_index, repeated.instruction_index);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,6 @@
+aoc_common::check_heap!(day_08, size: 1000, {
+    // 225_596 bytes at the peak, 142 allocations when measured
+    part1: { peak_bytes: 280_000, total_blocks: 180 },
+    // 321_484 bytes at the peak, 528 allocations when measured
+    part2: { peak_bytes: 400_000, total_blocks: 660 },
+});
@@ -0,0 +1,11 @@
+use aoc_common::generate;
+
+/// A synthetic puzzle input of `size` lines, the same for the same `seed`.
+pub fn input(seed: u64, size: usize) -> String {
+    let mut rng = generate::rng(seed);
+    // lines of numbers until the generator follows the puzzle's format
+    (0..size)
+        .map(|_| rng.u32(..1000).to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,57 @@
+use aoc_common::generate;
+
+/// `size` lines of two five-digit location ids, the lists sharing some of them.
+pub fn input(seed: u64, size: usize) -> String {
+    let mut rng = generate::rng(seed);
+    let left = (0..size)
+        .map(|_| rng.u32(10_000..100_000))
+        .collect::<Vec<_>>();
+    (0..size)
+        .map(|i| {
+            let right = match rng.u8(..4) {
+                0 => left[rng.usize(..size)],
+                _ => rng.u32(10_000..100_000),
+            };
+            format!("{}   {right}", left[i])
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    // the distance between the sorted lists, and the similarity counting the right list
+    // again for each left id
+    fn reference(input: &str) -> (u32, u32) {
+        let (mut left, mut right): (Vec<u32>, Vec<u32>) = input
+            .lines()
+            .map(|line| {
+                let (left, right) = line.split_once("   ").unwrap();
+                (left.parse::<u32>().unwrap(), right.parse::<u32>().unwrap())
+            })
+            .unzip();
+        let similarity = left
+            .iter()
+            .map(|id| id * right.iter().filter(|other| *other == id).count() as u32)
+            .sum();
+        left.sort_unstable();
+        right.sort_unstable();
+        let distance = left.iter().zip(&right).map(|(l, r)| l.abs_diff(*r)).sum();
+        (distance, similarity)
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(100)]
+    #[case(1000)]
+    fn test_solves_generated_input(#[case] size: usize) -> miette::Result<()> {
+        let input = input(generate::SEED, size);
+        let (part1, part2) = reference(&input);
+        assert_eq!(part1.to_string(), crate::part1::process(&input)?);
+        assert_eq!(part2.to_string(), crate::part2::process(&input)?);
+        Ok(())
+    }
+}
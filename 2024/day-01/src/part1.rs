@@ -8,12 +8,24 @@ use nom::{
     IResult,
 };
 
-use crate::custom_error::AocError;
+use aoc_common::{trace::detail, AocError};
 
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    let input = _input.trim();
-    let (_, pairs) = parse_input(input).map_err(|e| AocError::ParseError(e.to_string()))?;
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(parse(input)?)
+}
+
+/// Read the location ids of both lists, side by side.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<Vec<(u32, u32)>, AocError> {
+    let input = input.trim();
+    let (_, pairs) = parse_input(input).map_err(|e| AocError::from_nom(input, e))?;
+    Ok(pairs)
+}
+
+/// Sum the distances between the sorted lists.
+#[tracing::instrument(skip_all)]
+pub fn solve(pairs: Vec<(u32, u32)>) -> miette::Result<String, AocError> {
     let output = distances(pairs).iter().sum::<u32>();
     Ok(output.to_string())
 }
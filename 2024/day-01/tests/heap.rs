@@ -0,0 +1,6 @@
+aoc_common::check_heap!(day_01, size: 1000, {
+    // 16_336 bytes at the peak, 14 allocations when measured
+    part1: { peak_bytes: 21_000, total_blocks: 15 },
+    // 16_192 bytes at the peak, 12 allocations when measured
+    part2: { peak_bytes: 21_000, total_blocks: 15 },
+});
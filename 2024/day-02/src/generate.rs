@@ -0,0 +1,87 @@
+use aoc_common::generate::{self, Rng};
+
+/// `size` reports of five to eight levels, about half of them safe.
+pub fn input(seed: u64, size: usize) -> String {
+    let mut rng = generate::rng(seed);
+    (0..size)
+        .map(|_| {
+            let levels = rng.usize(5..=8);
+            let report = if rng.bool() {
+                safe(&mut rng, levels)
+            } else {
+                (0..levels).map(|_| rng.u32(1..100)).collect()
+            };
+            report
+                .iter()
+                .map(|level| level.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// levels all increasing or all decreasing by one to three
+fn safe(rng: &mut Rng, levels: usize) -> Vec<u32> {
+    let increasing = rng.bool();
+    let mut level = rng.u32(30..70);
+    (0..levels)
+        .map(|_| {
+            let current = level;
+            let step = rng.u32(1..=3);
+            level = if increasing {
+                level + step
+            } else {
+                level - step
+            };
+            current
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    // the safe reports, then those safe once any single level is removed
+    fn reference(input: &str) -> (usize, usize) {
+        let is_safe = |levels: &[i32]| {
+            let steps = levels.windows(2).map(|pair| pair[1] - pair[0]);
+            steps.clone().all(|step| (1..=3).contains(&step))
+                || steps.clone().all(|step| (-3..=-1).contains(&step))
+        };
+        let reports = input
+            .lines()
+            .map(|line| {
+                line.split(' ')
+                    .map(|level| level.parse().unwrap())
+                    .collect::<Vec<i32>>()
+            })
+            .collect::<Vec<_>>();
+        let safe = reports.iter().filter(|report| is_safe(report)).count();
+        let dampened = reports
+            .iter()
+            .filter(|report| {
+                (0..report.len()).any(|removed| {
+                    let mut report = report.to_vec();
+                    report.remove(removed);
+                    is_safe(&report)
+                })
+            })
+            .count();
+        (safe, dampened)
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(100)]
+    #[case(1000)]
+    fn test_solves_generated_input(#[case] size: usize) -> miette::Result<()> {
+        let input = input(generate::SEED, size);
+        let (part1, part2) = reference(&input);
+        assert_eq!(part1.to_string(), crate::part1::process(&input)?);
+        assert_eq!(part2.to_string(), crate::part2::process(&input)?);
+        Ok(())
+    }
+}
@@ -1,18 +1,29 @@
-use crate::custom_error::AocError;
+use aoc_common::{trace::detail, AocError};
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let reports = input
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(parse(input)?)
+}
+
+/// Read the levels of each report.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<Vec<Report>, AocError> {
+    input
         .lines()
-        .map(parse_line)
-        .collect::<Result<Vec<_>, _>>()?;
+        .map(|line| parse_line(input, line))
+        .collect()
+}
+
+/// Count the safe reports.
+#[tracing::instrument(skip_all)]
+pub fn solve(reports: Vec<Report>) -> miette::Result<String, AocError> {
     let safe_reports = reports.into_iter().filter(|r| report_type(r) != ReportType::UnSafe).count();
     Ok(safe_reports.to_string())
 }
 
-fn parse_line(line: &str) -> Result<Report, AocError> {
+fn parse_line(input: &str, line: &str) -> Result<Report, AocError> {
     line.split_whitespace()
-        .map(|n| n.parse::<u32>().map_err(|e| AocError::ParseError(e.to_string())))
+        .map(|n| n.parse::<u32>().map_err(|_| AocError::parse_error(input, n, "a number")))
         .collect()
 }
 
@@ -24,7 +35,7 @@ enum ReportType {
     SafeDecreasing,
 }
 
-type Report = Vec<u32>;
+pub type Report = Vec<u32>;
 
 fn classify_diff(diff: i32) -> ReportType {
     match diff {
@@ -1,19 +1,30 @@
-use miette::miette;
+use aoc_common::{trace::detail, AocError};
 use nom::{
     character::complete::{newline, space1},
     multi::separated_list1,
     IResult,
 };
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let (_, reports) =
-        parse(input).map_err(|e| miette!("error parsing input: {}", e.to_string()))?;
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(parse(input)?)
+}
+
+/// Read the levels of each report.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<Vec<Report>, AocError> {
+    let (_, reports) = parse_reports(input).map_err(|e| AocError::from_nom(input, e))?;
+    Ok(reports)
+}
+
+/// Count the reports that are safe with at most one level removed.
+#[tracing::instrument(skip_all)]
+pub fn solve(reports: Vec<Report>) -> miette::Result<String, AocError> {
     let safe_count = reports.iter().filter(|r| report_type(r).is_safe()).count();
     Ok(safe_count.to_string())
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Report>> {
+fn parse_reports(input: &str) -> IResult<&str, Vec<Report>> {
     separated_list1(
         newline,
         separated_list1(space1, nom::character::complete::u32),
@@ -36,7 +47,7 @@ impl ReportType {
     }
 }
 
-type Report = Vec<u32>;
+pub type Report = Vec<u32>;
 
 fn report_type(report: &Report) -> ReportType {
     one_removed(report)
@@ -110,7 +121,7 @@ mod tests {
     #[case("8 6 4 4 1", ReportType::SafeDecreasing)]
     #[case("1 3 6 7 9", ReportType::SafeIncreasing)]
     fn test_report_type(#[case] input: &str, #[case] expected: ReportType) {
-        let report = &parse(input).unwrap().1[0];
+        let report = &parse_reports(input).unwrap().1[0];
         assert_eq!(report_type(report), expected);
     }
 
@@ -0,0 +1,6 @@
+aoc_common::check_heap!(day_02, size: 1000, {
+    // 56_604 bytes at the peak, 3_010 allocations when measured
+    part1: { peak_bytes: 71_000, total_blocks: 3_800 },
+    // 56_632 bytes at the peak, 9_584 allocations when measured
+    part2: { peak_bytes: 71_000, total_blocks: 12_000 },
+});
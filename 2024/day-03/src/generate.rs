@@ -0,0 +1,83 @@
+use aoc_common::generate::{self, Rng};
+
+const JUNK: &[u8] = b"!@#$%^&*()[]{}<>,;:'? \nmuldon't_";
+
+/// Corrupted memory holding `size` instructions among junk: mostly `mul(a,b)` of up to
+/// three-digit operands, some `do()` and `don't()`, and some that are not quite valid.
+///
+/// The products are summed as `u32`, which holds them up to a few thousand instructions.
+pub fn input(seed: u64, size: usize) -> String {
+    let mut rng = generate::rng(seed);
+    let mut memory = String::new();
+    for _ in 0..size {
+        let len = rng.usize(..8);
+        memory.push_str(&generate::word(&mut rng, JUNK, len));
+        memory.push_str(&instruction(&mut rng));
+    }
+    memory
+}
+
+fn instruction(rng: &mut Rng) -> String {
+    let (a, b) = (rng.u32(1..1000), rng.u32(1..1000));
+    match rng.u8(..10) {
+        0 => "do()".to_string(),
+        1 => "don't()".to_string(),
+        2 => format!("mul[{a},{b}]"),
+        3 => format!("mul({a},{b}]"),
+        4 => format!("mul ( {a} , {b} )"),
+        _ => format!("mul({a},{b})"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    // an operand of one to three digits, and what follows it
+    fn operand(s: &str) -> Option<(u32, &str)> {
+        let digits = s.bytes().take_while(u8::is_ascii_digit).count();
+        (1..=3)
+            .contains(&digits)
+            .then(|| (s[..digits].parse().unwrap(), &s[digits..]))
+    }
+
+    // the product of the `mul(a,b)` starting `s`, when it is valid
+    fn mul(s: &str) -> Option<u32> {
+        let (a, s) = operand(s.strip_prefix("mul(")?)?;
+        let (b, s) = operand(s.strip_prefix(',')?)?;
+        s.starts_with(')').then_some(a * b)
+    }
+
+    // the products of the valid `mul(a,b)`, trying one at every position of the memory, all
+    // of them then only those enabled by the last `do()` or `don't()` before them
+    fn reference(input: &str) -> (u32, u32) {
+        let (mut all, mut enabled_only, mut enabled) = (0, 0, true);
+        for i in 0..input.len() {
+            let rest = &input[i..];
+            if rest.starts_with("do()") {
+                enabled = true;
+            } else if rest.starts_with("don't()") {
+                enabled = false;
+            } else if let Some(product) = mul(rest) {
+                all += product;
+                if enabled {
+                    enabled_only += product;
+                }
+            }
+        }
+        (all, enabled_only)
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(100)]
+    #[case(1000)]
+    fn test_solves_generated_input(#[case] size: usize) -> miette::Result<()> {
+        let input = input(generate::SEED, size);
+        let (part1, part2) = reference(&input);
+        assert_eq!(part1.to_string(), crate::part1::process(&input)?);
+        assert_eq!(part2.to_string(), crate::part2::process(&input)?);
+        Ok(())
+    }
+}
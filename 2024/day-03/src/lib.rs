@@ -1,3 +1,10 @@
+pub mod generate;
 pub mod part1;
 pub mod part2;
-pub mod custom_error;
\ No newline at end of file
+
+aoc_common::solutions! {
+    year: 2024,
+    day: 3,
+    1 => part1::process,
+    2 => part2::process,
+}
@@ -4,18 +4,30 @@ use nom::{
     bytes::complete::tag, sequence::{delimited, separated_pair}, IResult
 };
 
-use crate::custom_error::AocError;
+use aoc_common::{trace::detail, AocError};
 
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let (_, muls) = parse(input).map_err(|e| AocError::ParseError(e.to_string()))?;
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(parse(input)?)
+}
+
+/// Read the valid `mul` instructions out of the corrupted memory.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<Vec<Mul>, AocError> {
+    let (_, muls) = parse_muls(input).map_err(|e| AocError::from_nom(input, e))?;
+    Ok(muls)
+}
+
+/// Sum the products of the instructions.
+#[tracing::instrument(skip_all)]
+pub fn solve(muls: Vec<Mul>) -> miette::Result<String, AocError> {
     let result = muls.iter().map(|mul| mul.eval()).sum::<u32>();
     Ok(result.to_string())
 }
 
 #[derive(PartialEq)]
-struct Mul((u32, u32));
+pub struct Mul((u32, u32));
 
 impl Mul {
     fn new(a: u32, b: u32) -> Self {
@@ -32,8 +44,8 @@ impl fmt::Debug for Mul {
     }
 }
 
-#[tracing::instrument]
-fn parse(input: &str) -> IResult<&str, Vec<Mul>> {
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+fn parse_muls(input: &str) -> IResult<&str, Vec<Mul>> {
     let mut remaining = input;
     let mut result = vec![];
     while !remaining.is_empty() {
@@ -74,7 +86,7 @@ mod tests {
     #[case("mul(23,34)", Ok(("", vec![Mul::new(23, 34)])))]    
     #[case("fsd8rmul(3,4)f9834hmul(5,6)fsdhjf", Ok(("", vec![Mul::new(3, 4), Mul::new(5, 6)])))]
     fn test_parse(#[case] input: &str, #[case] expected: IResult<&str, Vec<Mul>>) {
-        let actual = parse(input);
+        let actual = parse_muls(input);
         assert_eq!(expected, actual);
     }
 
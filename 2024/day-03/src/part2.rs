@@ -1,4 +1,4 @@
-use miette::miette;
+use aoc_common::{trace::detail, AocError};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -26,40 +26,37 @@ impl From<&str> for InstructionParser {
     }
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let (_, result) = products(input).map_err(|e| miette!("parse failed {}", e))?;
-    Ok(result.to_string())
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(parse(input)?)
 }
 
-pub fn process2(input: &str, parser: InstructionParser) -> miette::Result<String> {
-    let instr_parser = match parser {
-        InstructionParser::ManyTill => instructions_manytill,
-        InstructionParser::While => instructions_while,
-    };
-    let (_, result) =
-        products_with(input, instr_parser).map_err(|e| miette!("parse failed {}", e))?;
-    Ok(result.to_string())
+pub fn process2(input: &str, parser: InstructionParser) -> miette::Result<String, AocError> {
+    solve(parse_with(input, parser)?)
 }
 
-#[derive(PartialEq, Debug, Clone)]
-enum Instruction {
-    Mul((u32, u32)),
-    Do,
-    Dont,
+/// Read the instructions out of the corrupted memory.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<Vec<Instruction>, AocError> {
+    parse_with(input, InstructionParser::While)
 }
 
-#[derive(PartialEq, Debug, Clone)]
-enum ShouldProcess {
-    Yes,
-    No,
+/// Read the instructions out of the corrupted memory with the given parser.
+pub fn parse_with(
+    input: &str,
+    parser: InstructionParser,
+) -> miette::Result<Vec<Instruction>, AocError> {
+    let instr_parser = match parser {
+        InstructionParser::ManyTill => instructions_manytill,
+        InstructionParser::While => instructions_while,
+    };
+    let (_, instructions) = instr_parser(input).map_err(|e| AocError::from_nom(input, e))?;
+    Ok(instructions)
 }
 
-fn products_with(
-    input: &str,
-    instr_parser: impl Fn(&str) -> IResult<&str, Vec<Instruction>>,
-) -> IResult<&str, u32> {
-    let (input, instructions) = instr_parser(input)?;
+/// Sum the products of the enabled instructions.
+#[tracing::instrument(skip_all)]
+pub fn solve(instructions: Vec<Instruction>) -> miette::Result<String, AocError> {
     let (_, result) = instructions.iter().fold(
         (ShouldProcess::Yes, 0),
         |(should_process, sum), instr| match instr {
@@ -74,30 +71,22 @@ fn products_with(
             Instruction::Dont => (ShouldProcess::No, sum),
         },
     );
-    Ok((input, result))
+    Ok(result.to_string())
 }
 
-#[tracing::instrument]
-fn products(input: &str) -> IResult<&str, u32> {
-    let (input, instructions) = instructions_while(input)?;
-    let (_, result) = instructions.iter().fold(
-        (ShouldProcess::Yes, 0),
-        |(should_process, sum), instr| match instr {
-            Instruction::Mul((a, b)) => {
-                if should_process == ShouldProcess::Yes {
-                    (should_process, sum + a * b)
-                } else {
-                    (should_process, sum)
-                }
-            }
-            Instruction::Do => (ShouldProcess::Yes, sum),
-            Instruction::Dont => (ShouldProcess::No, sum),
-        },
-    );
-    Ok((input, result))
+#[derive(PartialEq, Debug, Clone)]
+pub enum Instruction {
+    Mul((u32, u32)),
+    Do,
+    Dont,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+enum ShouldProcess {
+    Yes,
+    No,
 }
 
-#[allow(dead_code)]
 fn instructions_manytill(input: &str) -> IResult<&str, Vec<Instruction>> {
     let (input, instructions) =
         many1(many_till(anychar, instruction).map(|(_discarded, instr)| instr))(input)?;
@@ -106,7 +95,6 @@ fn instructions_manytill(input: &str) -> IResult<&str, Vec<Instruction>> {
     Ok((input, instructions))
 }
 
-#[allow(dead_code)]
 fn instructions_while(input: &str) -> IResult<&str, Vec<Instruction>> {
     let mut remaining = input;
     let mut result = vec![];
@@ -165,12 +153,16 @@ mod tests {
     use rstest::rstest;
 
     #[rstest]
-    #[case("mul(3,4)", Ok(("", 12)))]
-    #[case("mul(23,34)", Ok(("", 782)))]
-    #[case("fsd8rmul(3,4)f9834hmul(5,6)fsdhjf", Ok(("", 42)))]
-    fn test_products(#[case] input: &str, #[case] expected: IResult<&str, u32>) {
-        let actual = products(input);
-        assert_eq!(expected, actual);
+    #[case("mul(3,4)", "12")]
+    #[case("mul(23,34)", "782")]
+    #[case("fsd8rmul(3,4)f9834hmul(5,6)fsdhjf", "42")]
+    fn test_products(
+        #[case] input: &str,
+        #[case] expected: &str,
+        #[values(InstructionParser::While, InstructionParser::ManyTill)] parser: InstructionParser,
+    ) -> miette::Result<()> {
+        assert_eq!(expected, solve(parse_with(input, parser)?)?);
+        Ok(())
     }
 
     #[rstest]
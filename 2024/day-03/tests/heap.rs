@@ -0,0 +1,6 @@
+aoc_common::check_heap!(day_03, size: 1000, {
+    // 4_105 bytes at the peak, 9 allocations when measured
+    part1: { peak_bytes: 5_200, total_blocks: 12 },
+    // 6_152 bytes at the peak, 9 allocations when measured
+    part2: { peak_bytes: 7_700, total_blocks: 12 },
+});
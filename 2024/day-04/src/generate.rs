@@ -0,0 +1,61 @@
+use aoc_common::generate;
+
+/// A square word search of `size` rows and columns of the letters of `XMAS`.
+pub fn input(seed: u64, size: usize) -> String {
+    let mut rng = generate::rng(seed);
+    (0..size)
+        .map(|_| generate::word(&mut rng, b"XMAS", size))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    // the XMAS read from every cell in every direction
+    fn reference(input: &str) -> usize {
+        let grid = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+        let at = |row: isize, column: isize| {
+            let row = grid.get(usize::try_from(row).ok()?)?;
+            row.get(usize::try_from(column).ok()?).copied()
+        };
+        let mut count = 0;
+        for row in 0..grid.len() as isize {
+            for column in 0..grid[0].len() as isize {
+                for (dr, dc) in [
+                    (-1, -1),
+                    (-1, 0),
+                    (-1, 1),
+                    (0, -1),
+                    (0, 1),
+                    (1, -1),
+                    (1, 0),
+                    (1, 1),
+                ] {
+                    if (0..4)
+                        .all(|i| at(row + dr * i, column + dc * i) == Some(b"XMAS"[i as usize]))
+                    {
+                        count += 1;
+                    }
+                }
+            }
+        }
+        count
+    }
+
+    // part two is not solved yet
+    #[rstest]
+    #[case(1)]
+    #[case(10)]
+    #[case(100)]
+    fn test_solves_generated_input(#[case] size: usize) -> miette::Result<()> {
+        let input = input(generate::SEED, size);
+        assert_eq!(
+            reference(&input).to_string(),
+            crate::part1::process(&input)?
+        );
+        Ok(())
+    }
+}
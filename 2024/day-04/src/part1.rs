@@ -1,57 +1,53 @@
-use miette::miette;
-use nom::{
-    character::complete::{alpha1, newline},
-    error::{ErrorKind, FromExternalError},
-    multi::separated_list1,
-    IResult,
-};
+use aoc_common::{grid::Line, trace::detail, AocError, Grid};
 
-use crate::grid::*;
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(parse(input)?)
+}
+
+/// Read the letters of the word search.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<WordSearch, AocError> {
+    word_search(input)
+}
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let (_, grid) = grid(input).map_err(|e| miette!("failed to parse grid: {}", e))?;
+/// Count the occurrences of `XMAS` in every direction.
+#[tracing::instrument(skip_all)]
+pub fn solve(grid: WordSearch) -> miette::Result<String, AocError> {
     let search_terms = ["XMAS", "SAMX"];
     let count = count_occurences(&grid, &search_terms);
     Ok(count.to_string())
 }
 
-type WordSearch = Grid<char>;
+pub type WordSearch = Grid<char>;
 
-fn grid(input: &str) -> IResult<&str, WordSearch> {
-    let (input, rows) = separated_list1(newline, alpha1)(input)?;
-    let width = rows[0].len();
-    let cells: Vec<char> = rows.into_iter().flat_map(|s| s.chars()).collect();
-    let grid = Grid::new(cells, width).map_err(|e| {
-        nom::Err::Error(nom::error::Error::from_external_error(
-            input,
-            ErrorKind::Fail,
-            e,
-        ))
-    })?;
-    Ok((input, grid))
+fn word_search(input: &str) -> Result<WordSearch, AocError> {
+    Grid::parse(input, |c| c.is_ascii_alphabetic().then_some(c))
 }
 
 fn count_occurences(grid: &WordSearch, search_terms: &[&str]) -> usize {
-    grid.rows()
+    (0..grid.height())
+        .map(|y| grid.row(y))
         .chain(grid.columns())
         .chain(grid.diagonals())
-        .map(|part| count_windows_matches(part, search_terms))
+        .map(|line| count_line_matches(line, search_terms))
         .sum()
 }
 
-fn count_windows_matches(input: &[char], search_terms: &[&str]) -> usize {
-    let windows = input.windows(search_terms[0].len());
-    let strings = windows.map(|w| w.iter().collect::<String>());
-    strings
-        .filter(|s| search_terms.contains(&s.as_str()))
-        .count()
+fn count_line_matches(line: Line<char>, search_terms: &[&str]) -> usize {
+    let mut cells = line.values().copied();
+    let mut count = 0;
+    loop {
+        count += search_terms
+            .iter()
+            .filter(|term| term.chars().eq(cells.clone().take(term.len())))
+            .count();
+        if cells.next().is_none() {
+            return count;
+        }
+    }
 }
 
-#[cfg(test)]
-#[path = "grid.rs"]
-mod grid;
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,18 +67,16 @@ MXMXAXMASX";
         assert_eq!("18", process(input)?);
         Ok(())
     }
-}
 
-// implement Debut
-impl std::fmt::Debug for WordSearch {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "{}x{}", self.width, self.height)?;
-        for row in self.rows() {
-            for cell in row {
-                write!(f, "{}", cell)?;
+    #[test]
+    fn test_ragged_rows() {
+        let input = "MMMS\nMSA\nAMXS";
+        match process(input) {
+            Err(AocError::ParseError(e)) => {
+                assert_eq!(aoc_common::Location { line: 2, column: 1 }, e.location);
+                assert_eq!("a row of 4 cells", e.expected);
             }
-            writeln!(f)?;
+            other => panic!("unexpected result {other:?}"),
         }
-        Ok(())
     }
 }
@@ -1,5 +1,19 @@
-#[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String> {
+use aoc_common::{trace::detail, AocError};
+
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(parse(input)?)
+}
+
+/// Read the puzzle input.
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+pub fn parse(input: &str) -> miette::Result<&str, AocError> {
+    Ok(input)
+}
+
+/// Compute the answer from the parsed input.
+#[tracing::instrument(skip_all)]
+pub fn solve(_input: &str) -> miette::Result<String, AocError> {
     Ok("part2".to_string())
 }
 
@@ -0,0 +1,6 @@
+aoc_common::check_heap!(day_04, size: 100, {
+    // 40_399 bytes at the peak, 2 allocations when measured
+    part1: { peak_bytes: 51_000, total_blocks: 3 },
+    // 5 bytes at the peak, 1 allocation when measured
+    part2: { peak_bytes: 7, total_blocks: 2 },
+});
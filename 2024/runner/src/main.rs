@@ -0,0 +1,22 @@
+use std::path::Path;
+
+use aoc_common::Registry;
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+fn registry() -> Registry {
+    Registry::new()
+        .with(day_01::SOLUTIONS)
+        .with(day_02::SOLUTIONS)
+        .with(day_03::SOLUTIONS)
+        .with(day_04::SOLUTIONS)
+}
+
+fn main() -> miette::Result<()> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("runner lives inside the year directory");
+    aoc_runner::main(registry(), root)
+}
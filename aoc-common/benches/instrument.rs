@@ -0,0 +1,90 @@
+use std::io;
+
+use aoc_common::{
+    generate::{self, SEED},
+    trace::detail,
+};
+use tracing::Dispatch;
+use tracing_subscriber::{fmt, layer::SubscriberExt};
+
+fn main() {
+    // Run registered benchmarks: the same function bare and instrumented, recording its whole
+    // input or skipping it, without a subscriber and with one formatting every span.
+    divan::main();
+}
+
+const LENS: &[usize] = &[100, 10_000];
+
+fn input(len: usize) -> String {
+    generate::word(&mut generate::rng(SEED), b"abcdefghij\n", len)
+}
+
+fn count(input: &str) -> usize {
+    input.bytes().filter(|b| *b == b'\n').count()
+}
+
+fn bare(input: &str) -> usize {
+    count(input)
+}
+
+#[tracing::instrument]
+fn recorded(input: &str) -> usize {
+    count(input)
+}
+
+#[tracing::instrument(skip(input), fields(input = detail(input)))]
+fn skipped(input: &str) -> usize {
+    count(input)
+}
+
+// formats every span like `just solve` would with `RUST_LOG=trace`, into the void
+fn subscriber() -> Dispatch {
+    let layer = fmt::layer()
+        .with_span_events(fmt::format::FmtSpan::NEW)
+        .with_writer(io::sink);
+    Dispatch::new(tracing_subscriber::registry().with(layer))
+}
+
+#[divan::bench_group]
+mod unsubscribed {
+    use super::*;
+
+    #[divan::bench(args = LENS)]
+    fn bare(bencher: divan::Bencher, len: usize) {
+        let input = input(len);
+        bencher.bench(|| super::bare(divan::black_box(&input)));
+    }
+
+    #[divan::bench(args = LENS)]
+    fn recorded(bencher: divan::Bencher, len: usize) {
+        let input = input(len);
+        bencher.bench(|| super::recorded(divan::black_box(&input)));
+    }
+
+    #[divan::bench(args = LENS)]
+    fn skipped(bencher: divan::Bencher, len: usize) {
+        let input = input(len);
+        bencher.bench(|| super::skipped(divan::black_box(&input)));
+    }
+}
+
+#[divan::bench_group]
+mod subscribed {
+    use super::*;
+
+    #[divan::bench(args = LENS)]
+    fn recorded(bencher: divan::Bencher, len: usize) {
+        let input = input(len);
+        tracing::dispatcher::with_default(&subscriber(), || {
+            bencher.bench(|| super::recorded(divan::black_box(&input)))
+        });
+    }
+
+    #[divan::bench(args = LENS)]
+    fn skipped(bencher: divan::Bencher, len: usize) {
+        let input = input(len);
+        tracing::dispatcher::with_default(&subscriber(), || {
+            bencher.bench(|| super::skipped(divan::black_box(&input)))
+        });
+    }
+}
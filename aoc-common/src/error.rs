@@ -0,0 +1,416 @@
+use std::{fmt::Display, path::PathBuf};
+
+use miette::{Diagnostic, LabeledSpan, NamedSource, SourceSpan};
+use nom_locate::LocatedSpan;
+use thiserror::Error;
+
+/// The error type shared by every solver of every year.
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("missing puzzle input `{}`", path.display())]
+    #[diagnostic(
+        code(aoc::missing_input),
+        help("fetch it with `just get-input day-XX`, or pass another file with `--input`")
+    )]
+    MissingInput { path: PathBuf },
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ParseError(Box<ParseError>),
+
+    #[error("{0}")]
+    #[diagnostic(code(aoc::logic_error))]
+    LogicError(String),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    LoopDetected(Box<LoopError>),
+
+    #[error("input has no solution: {0}")]
+    #[diagnostic(code(aoc::unsolvable))]
+    Unsolvable(String),
+
+    #[error("arithmetic overflow while computing {0}")]
+    #[diagnostic(code(aoc::overflow))]
+    Overflow(String),
+}
+
+/// A parse failure, rendered with a snippet of the input pointing at the offending text.
+#[derive(Error, Diagnostic, Debug)]
+#[error("parse error at {location}: expected {expected}, found `{found}`")]
+#[diagnostic(code(aoc::parse_error))]
+pub struct ParseError {
+    #[source_code]
+    pub input: NamedSource<String>,
+    #[label("expected {expected}")]
+    pub span: SourceSpan,
+    pub location: Location,
+    pub expected: String,
+    pub found: String,
+}
+
+/// A walk that came back to a state it already left, so goes round forever, rendered with the
+/// definitions of the nodes of its cycle in the input.
+#[derive(Error, Diagnostic, Debug)]
+#[error("loop detected: after {prefix} steps, the walk goes round a cycle of {length} steps")]
+#[diagnostic(code(aoc::loop_detected))]
+pub struct LoopError {
+    #[source_code]
+    pub input: NamedSource<String>,
+    #[label(collection)]
+    pub labels: Vec<LabeledSpan>,
+    #[help]
+    pub path: String,
+    /// The states of the cycle from the one the walk entered it by: each node with the index
+    /// of the instruction followed from it.
+    pub cycle: Vec<(String, usize)>,
+    /// Steps in the cycle.
+    pub length: u64,
+    /// Steps before the walk entered the cycle.
+    pub prefix: u64,
+    /// What else keeps the walk away from its end, e.g. found by analysing the input.
+    #[related]
+    pub related: Vec<AocError>,
+}
+
+/// A 1-based line and column in the puzzle input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    /// Locate the byte `offset` within `source`, an offset inside a character standing on it.
+    pub fn from_offset(source: &str, offset: usize) -> Self {
+        let mut offset = offset.min(source.len());
+        while !source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let consumed = &source[..offset];
+        let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+        Self {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+// how much of the remaining input is quoted in a parse error
+const FOUND_LEN: usize = 16;
+
+// how many states of a cycle are labeled and spelled out in a loop error
+const LOOP_STATES: usize = 8;
+
+/// Parser input that knows where it stands within the puzzle input.
+pub trait Positioned {
+    /// Byte offset within `source`, the input the parser started from.
+    fn offset_in(&self, source: &str) -> usize;
+    /// What is left to parse.
+    fn rest(&self) -> &str;
+}
+
+impl Positioned for &str {
+    // a subslice of `source` is located exactly, anything else is taken as one of its suffixes
+    fn offset_in(&self, source: &str) -> usize {
+        let start = source.as_ptr() as usize;
+        let position = self.as_ptr() as usize;
+        if start <= position && position + self.len() <= start + source.len() {
+            position - start
+        } else {
+            source.len().saturating_sub(self.len())
+        }
+    }
+
+    fn rest(&self) -> &str {
+        self
+    }
+}
+
+impl<X> Positioned for LocatedSpan<&str, X> {
+    fn offset_in(&self, _source: &str) -> usize {
+        self.location_offset()
+    }
+
+    fn rest(&self) -> &str {
+        self.fragment()
+    }
+}
+
+impl AocError {
+    /// Build a parse error at `rest`, the part of `source` where parsing stopped.
+    pub fn parse_error(source: &str, rest: impl Positioned, expected: impl Into<String>) -> Self {
+        let offset = rest.offset_in(source).min(source.len());
+        let found = rest.rest().lines().next().unwrap_or_default();
+        // highlight the offending word, or a single character when it starts with a space
+        let highlighted = found
+            .split_whitespace()
+            .next()
+            .filter(|word| found.starts_with(word))
+            .map_or_else(|| found.chars().next().map_or(0, char::len_utf8), str::len);
+        AocError::ParseError(Box::new(ParseError {
+            input: NamedSource::new("input", source.to_string()),
+            span: (offset, highlighted).into(),
+            location: Location::from_offset(source, offset),
+            expected: expected.into(),
+            found: found.chars().take(FOUND_LEN).collect(),
+        }))
+    }
+
+    /// Build a loop error for a walk over `source` that entered `cycle` after `prefix` steps,
+    /// each state of the cycle being a node, the index of the instruction followed from it and
+    /// the span of the node's definition.
+    pub fn loop_detected(
+        source: &str,
+        prefix: u64,
+        cycle: impl IntoIterator<Item = (String, usize, SourceSpan)>,
+    ) -> Self {
+        let mut labels = Vec::<LabeledSpan>::new();
+        let mut states = Vec::new();
+        for (step, (node, instruction, span)) in cycle.into_iter().enumerate() {
+            // a node only labeled by the first state standing on it
+            if step < LOOP_STATES && labels.iter().all(|label| label.inner() != &span) {
+                let label = match step {
+                    0 => format!("enters the cycle with instruction #{instruction}"),
+                    _ => format!("step {step} of the cycle, with instruction #{instruction}"),
+                };
+                labels.push(LabeledSpan::new_with_span(Some(label), span));
+            }
+            states.push((node, instruction));
+        }
+        let mut path = states
+            .iter()
+            .take(LOOP_STATES)
+            .map(|(node, instruction)| format!("{node} #{instruction}"))
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        if states.len() > LOOP_STATES {
+            path.push_str(&format!(" -> ... {} more", states.len() - LOOP_STATES));
+        }
+        if let Some((node, instruction)) = states.first() {
+            path.push_str(&format!(" -> back to {node} #{instruction}"));
+        }
+        AocError::LoopDetected(Box::new(LoopError {
+            input: NamedSource::new("input", source.to_string()),
+            labels,
+            path: format!("the cycle goes {path}"),
+            length: states.len() as u64,
+            cycle: states,
+            prefix,
+            related: Vec::new(),
+        }))
+    }
+
+    /// Convert a nom error raised while parsing `source`.
+    pub fn from_nom<I: Positioned>(source: &str, err: nom::Err<nom::error::Error<I>>) -> Self {
+        match err {
+            nom::Err::Incomplete(_) => {
+                Self::parse_error(source, &source[source.len()..], "more input")
+            }
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                Self::parse_error(source, e.input, e.code.description())
+            }
+        }
+    }
+
+    /// Name the input a parse error points into, e.g. with the path it was read from.
+    pub fn with_source_name(self, name: impl AsRef<str>) -> Self {
+        match self {
+            AocError::ParseError(mut e) => {
+                e.input = NamedSource::new(name, e.input.inner().clone());
+                AocError::ParseError(e)
+            }
+            AocError::LoopDetected(mut e) => {
+                e.input = NamedSource::new(name, e.input.inner().clone());
+                AocError::LoopDetected(e)
+            }
+            other => other,
+        }
+    }
+
+    /// Relate `related` to a loop error, explaining why the walk goes round; any other error is
+    /// left as it is.
+    pub fn with_related(self, related: AocError) -> Self {
+        match self {
+            AocError::LoopDetected(mut e) => {
+                e.related.push(related);
+                AocError::LoopDetected(e)
+            }
+            other => other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{character::complete::digit1, sequence::terminated, IResult};
+    use nom_locate::LocatedSpan;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("abc", 0, Location { line: 1, column: 1 })]
+    #[case("abc", 2, Location { line: 1, column: 3 })]
+    #[case("abc\ndef", 4, Location { line: 2, column: 1 })]
+    #[case("abc\ndef\nghi", 9, Location { line: 3, column: 2 })]
+    #[case("abc", 10, Location { line: 1, column: 4 })]
+    // inside the two bytes of é, then past it
+    #[case("\né", 2, Location { line: 2, column: 1 })]
+    #[case("\néa", 3, Location { line: 2, column: 2 })]
+    fn test_location_from_offset(
+        #[case] source: &str,
+        #[case] offset: usize,
+        #[case] expected: Location,
+    ) {
+        assert_eq!(expected, Location::from_offset(source, offset));
+    }
+
+    fn numbers(input: &str) -> IResult<&str, &str> {
+        terminated(digit1, nom::character::complete::newline)(input)
+    }
+
+    #[test]
+    fn test_from_nom() {
+        let source = "12\nab";
+        let (rest, _) = numbers(source).unwrap();
+        let err = AocError::from_nom(source, numbers(rest).unwrap_err());
+        match err {
+            AocError::ParseError(e) => {
+                assert_eq!(Location { line: 2, column: 1 }, e.location);
+                assert_eq!(SourceSpan::from((3, 2)), e.span);
+                assert_eq!("Digit", e.expected);
+                assert_eq!("ab", e.found);
+            }
+            other => panic!("unexpected error {other:?}"),
+        }
+    }
+
+    fn located_numbers(input: LocatedSpan<&str>) -> IResult<LocatedSpan<&str>, LocatedSpan<&str>> {
+        terminated(digit1, nom::character::complete::newline)(input)
+    }
+
+    #[test]
+    fn test_from_nom_located() {
+        let source = "12\n34\n5x";
+        let (rest, _) = located_numbers(LocatedSpan::new(source)).unwrap();
+        let (rest, _) = located_numbers(rest).unwrap();
+        let err = AocError::from_nom(source, located_numbers(rest).unwrap_err());
+        assert!(matches!(
+            err,
+            AocError::ParseError(e) if e.location == Location { line: 3, column: 2 }
+        ));
+    }
+
+    #[test]
+    fn test_parse_error_in_line() {
+        let source = "1 2\n3 x";
+        let line = source.lines().nth(1).unwrap();
+        let err = AocError::parse_error(source, &line[2..], "a number");
+        assert!(matches!(
+            err,
+            AocError::ParseError(e) if e.location == Location { line: 2, column: 3 }
+        ));
+    }
+
+    #[test]
+    fn test_parse_error_display() {
+        let err = AocError::parse_error("1 2\n3 x", "x", "a number");
+        assert_eq!(
+            "parse error at line 2, column 3: expected a number, found `x`",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_error_snippet() {
+        let err = AocError::parse_error("1 2\n3 x4 5", "x4 5", "a number")
+            .with_source_name("day-02/input1.txt");
+        let mut rendered = String::new();
+        miette::GraphicalReportHandler::new_themed(miette::GraphicalTheme::unicode_nocolor())
+            .render_report(&mut rendered, &err)
+            .unwrap();
+        assert!(rendered.contains("[day-02/input1.txt:2:3]"), "{rendered}");
+        assert!(rendered.contains("2 │ 3 x4 5"), "{rendered}");
+        assert!(rendered.contains("expected a number"), "{rendered}");
+    }
+
+    // a loop through `A = (B, B)` and `B = (A, A)`, standing on each node with both instructions
+    fn loop_error(states: usize) -> AocError {
+        let source = "LR\n\nA = (B, B)\nB = (A, A)";
+        let cycle = (0..states).map(|step| {
+            let (node, span) = if step % 2 == 0 {
+                ("A", (4, 10))
+            } else {
+                ("B", (15, 10))
+            };
+            (node.to_string(), step % 2, span.into())
+        });
+        AocError::loop_detected(source, 3, cycle)
+    }
+
+    #[test]
+    fn test_loop_detected() {
+        match loop_error(20) {
+            AocError::LoopDetected(e) => {
+                assert_eq!(3, e.prefix);
+                assert_eq!(20, e.length);
+                assert_eq!(("A".to_string(), 0), e.cycle[0]);
+                assert_eq!(2, e.labels.len());
+                assert_eq!(
+                    "the cycle goes A #0 -> B #1 -> A #0 -> B #1 -> A #0 -> B #1 -> A #0 -> B #1 \
+                     -> ... 12 more -> back to A #0",
+                    e.path
+                );
+            }
+            other => panic!("unexpected error {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_loop_detected_snippet() {
+        let err = loop_error(2).with_source_name("day-08/input1.txt");
+        let mut rendered = String::new();
+        miette::GraphicalReportHandler::new_themed(miette::GraphicalTheme::unicode_nocolor())
+            .render_report(&mut rendered, &err)
+            .unwrap();
+        assert!(rendered.contains("[day-08/input1.txt:3:1]"), "{rendered}");
+        assert!(rendered.contains("3 │ A = (B, B)"), "{rendered}");
+        assert!(
+            rendered.contains("enters the cycle with instruction #0"),
+            "{rendered}"
+        );
+        assert!(
+            rendered.contains("step 1 of the cycle, with instruction #1"),
+            "{rendered}"
+        );
+        assert!(rendered.contains("back to A #0"), "{rendered}");
+    }
+
+    #[test]
+    fn test_loop_detected_related() {
+        let err =
+            loop_error(2).with_related(AocError::Unsolvable("no end can be reached".to_string()));
+        let mut rendered = String::new();
+        miette::GraphicalReportHandler::new_themed(miette::GraphicalTheme::unicode_nocolor())
+            .render_report(&mut rendered, &err)
+            .unwrap();
+        assert!(
+            rendered.contains("input has no solution: no end can be reached"),
+            "{rendered}"
+        );
+        // only a loop error relates others
+        let err = AocError::LogicError("no walk".to_string())
+            .with_related(AocError::Unsolvable("no end can be reached".to_string()));
+        assert!(matches!(err, AocError::LogicError(_)));
+    }
+}
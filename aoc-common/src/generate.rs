@@ -0,0 +1,57 @@
+//! Seeded generators of synthetic puzzle inputs, to see how solutions scale with their input.
+//!
+//! Each day crate exposes `generate::input(seed, size)` built on these helpers, the meaning of
+//! `size` (lines, nodes, grid side...) depending on the puzzle.
+
+pub use fastrand::Rng;
+
+/// Seed of the inputs generated by benches, for their results to be comparable across runs.
+pub const SEED: u64 = 20_231_201;
+
+/// A random generator producing the same values for the same `seed`.
+pub fn rng(seed: u64) -> Rng {
+    Rng::with_seed(seed)
+}
+
+/// `len` characters picked from the ASCII `alphabet`.
+pub fn word(rng: &mut Rng, alphabet: &[u8], len: usize) -> String {
+    (0..len)
+        .map(|_| char::from(alphabet[rng.usize(..alphabet.len())]))
+        .collect()
+}
+
+/// `len` distinct values picked from `range`, in random order.
+pub fn distinct(rng: &mut Rng, range: std::ops::Range<u32>, len: usize) -> Vec<u32> {
+    let mut values = range.collect::<Vec<_>>();
+    rng.shuffle(&mut values);
+    values.truncate(len);
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_values() {
+        let words = |seed| {
+            let mut rng = rng(seed);
+            (0..10)
+                .map(|len| word(&mut rng, b"abc", len))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(words(SEED), words(SEED));
+        assert_ne!(words(SEED), words(SEED + 1));
+        assert!(words(SEED).concat().chars().all(|c| "abc".contains(c)));
+    }
+
+    #[test]
+    fn test_distinct() {
+        let mut values = distinct(&mut rng(SEED), 10..20, 5);
+        assert_eq!(5, values.len());
+        values.sort();
+        values.dedup();
+        assert_eq!(5, values.len());
+        assert!(values.iter().all(|value| (10..20).contains(value)));
+    }
+}
@@ -0,0 +1,487 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::AocError;
+
+/// A cell of a grid, `x` grows to the right and `y` downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pos {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Pos {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// Move by `offset`, `None` when it would leave the positive quadrant.
+    pub fn checked_add(self, offset: Offset) -> Option<Pos> {
+        Some(Pos {
+            x: self.x.checked_add_signed(offset.dx)?,
+            y: self.y.checked_add_signed(offset.dy)?,
+        })
+    }
+}
+
+/// A signed move between two cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Offset {
+    pub dx: isize,
+    pub dy: isize,
+}
+
+impl Offset {
+    pub const N: Offset = Offset::new(0, -1);
+    pub const NE: Offset = Offset::new(1, -1);
+    pub const E: Offset = Offset::new(1, 0);
+    pub const SE: Offset = Offset::new(1, 1);
+    pub const S: Offset = Offset::new(0, 1);
+    pub const SW: Offset = Offset::new(-1, 1);
+    pub const W: Offset = Offset::new(-1, 0);
+    pub const NW: Offset = Offset::new(-1, -1);
+
+    /// The 4 orthogonal neighbours, clockwise from north.
+    pub const ORTHOGONAL: [Offset; 4] = [Offset::N, Offset::E, Offset::S, Offset::W];
+    /// The 8 neighbours including diagonals, clockwise from north.
+    pub const ALL: [Offset; 8] = [
+        Offset::N,
+        Offset::NE,
+        Offset::E,
+        Offset::SE,
+        Offset::S,
+        Offset::SW,
+        Offset::W,
+        Offset::NW,
+    ];
+
+    pub const fn new(dx: isize, dy: isize) -> Self {
+        Self { dx, dy }
+    }
+}
+
+/// A rectangular grid stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(cells: Vec<T>, width: usize) -> Result<Self, AocError> {
+        if width == 0 || !cells.len().is_multiple_of(width) {
+            return Err(AocError::LogicError(
+                "data length is not a multiple of width".to_string(),
+            ));
+        }
+        let height = cells.len() / width;
+        Ok(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Parse one cell per character and one row per line, `cell` maps each character.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, AocError> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        for line in input.lines() {
+            let row_width = *width.get_or_insert(line.chars().count());
+            if line.chars().count() != row_width {
+                return Err(AocError::parse_error(
+                    input,
+                    line,
+                    format!("a row of {row_width} cells"),
+                ));
+            }
+            for (i, c) in line.char_indices() {
+                let value = cell(c)
+                    .ok_or_else(|| AocError::parse_error(input, &line[i..], "a grid cell"))?;
+                cells.push(value);
+            }
+        }
+        match width {
+            Some(width) if width > 0 => Self::new(cells, width),
+            _ => Err(AocError::parse_error(input, input, "at least one row")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[self.cell_index(pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            let index = self.cell_index(pos);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// Replace the cell at `pos`, returning the previous value or `None` when out of bounds.
+    pub fn set(&mut self, pos: Pos, value: T) -> Option<T> {
+        self.get_mut(pos).map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// Move `pos` by `offset`, `None` when the result is outside the grid.
+    pub fn offset(&self, pos: Pos, offset: Offset) -> Option<Pos> {
+        pos.checked_add(offset).filter(|&pos| self.contains(pos))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Pos::new(i % width, i / width))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Pos, &mut T)> {
+        self.positions().zip(&mut self.cells)
+    }
+
+    /// The cells reached by `offsets` from `pos`, skipping those outside the grid.
+    pub fn neighbours<'a>(
+        &'a self,
+        pos: Pos,
+        offsets: &'a [Offset],
+    ) -> impl Iterator<Item = (Pos, &'a T)> + 'a {
+        offsets.iter().filter_map(move |&offset| {
+            let pos = self.offset(pos, offset)?;
+            Some((pos, &self[pos]))
+        })
+    }
+
+    /// The orthogonal neighbours of `pos`.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        self.neighbours(pos, &Offset::ORTHOGONAL)
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos`.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        self.neighbours(pos, &Offset::ALL)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    fn cell_index(&self, pos: Pos) -> usize {
+        pos.y * self.width + pos.x
+    }
+}
+
+/// A straight run of cells walked lazily from a start position by a fixed step,
+/// up to the edge of the grid.
+pub struct Line<'a, T> {
+    grid: &'a Grid<T>,
+    next: Option<Pos>,
+    step: Offset,
+}
+
+impl<'a, T> Line<'a, T> {
+    /// The cells without their positions.
+    pub fn values(self) -> impl Iterator<Item = &'a T> + Clone {
+        self.map(|(_, value)| value)
+    }
+}
+
+// not derived, a line is cloneable whatever its cells are
+impl<T> Clone for Line<'_, T> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<'a, T> Iterator for Line<'a, T> {
+    type Item = (Pos, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.next?;
+        self.next = self.grid.offset(pos, self.step);
+        Some((pos, &self.grid[pos]))
+    }
+}
+
+impl<T> Grid<T> {
+    /// The cells from `start` onwards in the direction of `step`.
+    pub fn line(&self, start: Pos, step: Offset) -> Line<'_, T> {
+        Line {
+            grid: self,
+            next: Some(start).filter(|&start| self.contains(start)),
+            step,
+        }
+    }
+
+    pub fn row(&self, y: usize) -> Line<'_, T> {
+        self.line(Pos::new(0, y), Offset::E)
+    }
+
+    pub fn column(&self, x: usize) -> Line<'_, T> {
+        self.line(Pos::new(x, 0), Offset::S)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = Line<'_, T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Diagonals going down-left, from the top-left corner to the bottom-right one.
+    pub fn diagonals_down_left(&self) -> impl Iterator<Item = Line<'_, T>> {
+        let (width, height) = (self.width, self.height);
+        let top = (0..width).map(|x| Pos::new(x, 0));
+        let right = (1..height).map(move |y| Pos::new(width - 1, y));
+        top.chain(right).map(|start| self.line(start, Offset::SW))
+    }
+
+    /// Diagonals going down-right, from the top-right corner to the bottom-left one.
+    pub fn diagonals_down_right(&self) -> impl Iterator<Item = Line<'_, T>> {
+        let top = (0..self.width).rev().map(|x| Pos::new(x, 0));
+        let left = (1..self.height).map(|y| Pos::new(0, y));
+        top.chain(left).map(|start| self.line(start, Offset::SE))
+    }
+
+    /// Every diagonal in both directions, down-left ones first.
+    pub fn diagonals(&self) -> impl Iterator<Item = Line<'_, T>> {
+        self.diagonals_down_left()
+            .chain(self.diagonals_down_right())
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        assert!(self.contains(pos), "{pos:?} is outside the grid");
+        &self.cells[self.cell_index(pos)]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        assert!(self.contains(pos), "{pos:?} is outside the grid");
+        let index = self.cell_index(pos);
+        &mut self.cells[index]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn test_new() -> miette::Result<()> {
+        let grid = Grid::new(vec![1, 2, 3, 4], 2)?;
+        assert_eq!(grid.cells(), [1, 2, 3, 4]);
+        assert_eq!(grid.width(), 2);
+        assert_eq!(grid.height(), 2);
+        assert!(Grid::new(vec![1, 2, 3], 2).is_err());
+        assert!(Grid::<u8>::new(vec![], 0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_get() -> miette::Result<()> {
+        let grid = Grid::new(vec![1, 2, 3, 4], 2)?;
+        assert_eq!(grid.get(Pos::new(0, 0)), Some(&1));
+        assert_eq!(grid.get(Pos::new(1, 0)), Some(&2));
+        assert_eq!(grid.get(Pos::new(0, 1)), Some(&3));
+        assert_eq!(grid.get(Pos::new(1, 1)), Some(&4));
+        assert_eq!(grid.get(Pos::new(2, 0)), None);
+        assert_eq!(grid.get(Pos::new(0, 2)), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_and_get_mut() -> miette::Result<()> {
+        let mut grid = Grid::new(vec![1, 2, 3, 4], 2)?;
+        assert_eq!(Some(2), grid.set(Pos::new(1, 0), 5));
+        assert_eq!(None, grid.set(Pos::new(2, 0), 6));
+        *grid.get_mut(Pos::new(0, 1)).unwrap() += 10;
+        grid[Pos::new(1, 1)] = 0;
+        assert_eq!(grid.cells(), [1, 5, 13, 0]);
+        assert!(grid.get_mut(Pos::new(0, 2)).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter() -> miette::Result<()> {
+        let grid = Grid::new(vec!['a', 'b', 'c', 'd', 'e', 'f'], 3)?;
+        let (pos, _) = grid.iter().find(|(_, &c)| c == 'e').unwrap();
+        assert_eq!(Pos::new(1, 1), pos);
+        assert_eq!(6, grid.positions().count());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(Pos::new(1, 1), Offset::NW, Some(Pos::new(0, 0)))]
+    #[case(Pos::new(0, 0), Offset::W, None)]
+    #[case(Pos::new(0, 0), Offset::N, None)]
+    #[case(Pos::new(2, 2), Offset::SE, None)]
+    #[case(Pos::new(0, 2), Offset::new(2, -2), Some(Pos::new(2, 0)))]
+    #[case(Pos::new(0, 0), Offset::new(3, 0), None)]
+    fn test_offset(#[case] pos: Pos, #[case] offset: Offset, #[case] expected: Option<Pos>) {
+        let grid = Grid::new((1..=9).collect(), 3).unwrap();
+        assert_eq!(expected, grid.offset(pos, offset));
+    }
+
+    #[rstest]
+    #[case(Pos::new(1, 1), vec![2, 6, 8, 4], vec![2, 3, 6, 9, 8, 7, 4, 1])]
+    #[case(Pos::new(0, 0), vec![2, 4], vec![2, 5, 4])]
+    #[case(Pos::new(2, 1), vec![3, 9, 5], vec![3, 9, 8, 5, 2])]
+    fn test_neighbours(#[case] pos: Pos, #[case] expected4: Vec<i32>, #[case] expected8: Vec<i32>) {
+        let grid = Grid::new((1..=9).collect(), 3).unwrap();
+        let values =
+            |it: &mut dyn Iterator<Item = (Pos, &i32)>| it.map(|(_, &v)| v).collect::<Vec<_>>();
+        assert_eq!(expected4, values(&mut grid.neighbours4(pos)));
+        assert_eq!(expected8, values(&mut grid.neighbours8(pos)));
+    }
+
+    #[test]
+    fn test_rows() -> miette::Result<()> {
+        let grid = Grid::new(vec![1, 2, 3, 4], 2)?;
+        let rows: Vec<_> = grid.rows().map(|row| row.to_vec()).collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows, vec![vec![1, 2], vec![3, 4]]);
+        Ok(())
+    }
+
+    fn values<'a>(lines: impl Iterator<Item = Line<'a, i32>>) -> Vec<Vec<i32>> {
+        lines.map(|line| line.values().copied().collect()).collect()
+    }
+
+    #[test]
+    fn test_columns() -> miette::Result<()> {
+        let grid = Grid::new((1..=6).collect(), 3)?;
+        let columns = values(grid.columns());
+        assert_eq!(columns.len(), 3);
+        assert_eq!(columns, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_all_diagonals() -> miette::Result<()> {
+        let grid = Grid::new((1..=9).collect(), 3)?;
+        let diagonals = values(grid.diagonals());
+        assert_eq!(diagonals.len(), 10);
+        assert_eq!(
+            diagonals,
+            vec![
+                vec![1],
+                vec![2, 4],
+                vec![3, 5, 7],
+                vec![6, 8],
+                vec![9],
+                vec![3],
+                vec![2, 6],
+                vec![1, 5, 9],
+                vec![4, 8],
+                vec![7]
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_diagonals_of_wide_grid() -> miette::Result<()> {
+        let grid = Grid::new((1..=8).collect(), 4)?;
+        assert_eq!(
+            values(grid.diagonals_down_left()),
+            vec![vec![1], vec![2, 5], vec![3, 6], vec![4, 7], vec![8]]
+        );
+        assert_eq!(
+            values(grid.diagonals_down_right()),
+            vec![vec![4], vec![3, 8], vec![2, 7], vec![1, 6], vec![5]]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_line_positions() -> miette::Result<()> {
+        let grid = Grid::new((1..=9).collect(), 3)?;
+        let line = grid.line(Pos::new(2, 0), Offset::SW);
+        assert_eq!(
+            vec![
+                (Pos::new(2, 0), &3),
+                (Pos::new(1, 1), &5),
+                (Pos::new(0, 2), &7)
+            ],
+            line.collect::<Vec<_>>()
+        );
+        assert_eq!(0, grid.line(Pos::new(3, 0), Offset::E).count());
+        assert_eq!(vec![&4, &5, &6], grid.row(1).values().collect::<Vec<_>>());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_and_display() -> miette::Result<()> {
+        let input = "#.#\n..#\n";
+        let grid = Grid::parse(input, |c| match c {
+            '#' => Some(1),
+            '.' => Some(0),
+            _ => None,
+        })?;
+        assert_eq!(grid.cells(), [1, 0, 1, 0, 0, 1]);
+        assert_eq!("101\n001", grid.to_string());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("#.#\n.#\n", 2, 1, "a row of 3 cells")]
+    #[case("#.#\n.x#\n", 2, 2, "a grid cell")]
+    #[case("", 1, 1, "at least one row")]
+    fn test_parse_error(
+        #[case] input: &str,
+        #[case] line: usize,
+        #[case] column: usize,
+        #[case] expected: &str,
+    ) {
+        let err = Grid::parse(input, |c| "#.".contains(c).then_some(c)).unwrap_err();
+        match err {
+            AocError::ParseError(e) => {
+                assert_eq!(crate::Location { line, column }, e.location);
+                assert_eq!(expected, e.expected);
+            }
+            other => panic!("unexpected error {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Heap usage ceilings of solutions, checked in tests with dhat's testing mode.
+//!
+//! dhat counts the allocations of the whole process and runs a single profiler at a time, so
+//! each day checks its ceilings in a single test of its own test binary, `tests/heap.rs`,
+//! which uses [`Alloc`] as its global allocator: [`check_heap!`](crate::check_heap) writes both.
+
+use std::fmt::Display;
+
+use crate::AocError;
+
+pub use dhat::Alloc;
+
+/// What a solution allocated on the heap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Usage {
+    /// Bytes live when the heap was at its largest.
+    pub peak_bytes: usize,
+    /// Allocations made over the whole run.
+    pub total_blocks: u64,
+}
+
+impl Display for Usage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} bytes at the peak, {} allocations",
+            self.peak_bytes, self.total_blocks
+        )
+    }
+}
+
+/// The most a solution may allocate, in the same terms as [`Usage`].
+pub type Ceiling = Usage;
+
+/// Run `f` under dhat's testing profiler and report what it allocated.
+///
+/// # Panics
+///
+/// When another profiler is running or [`Alloc`] is not the global allocator.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    let profiler = dhat::Profiler::builder().testing().build();
+    let value = f();
+    let stats = dhat::HeapStats::get();
+    drop(profiler);
+
+    assert!(
+        stats.total_blocks > 0 || counts_allocations(),
+        "dhat counts no allocation, the test needs \
+         `#[global_allocator] static ALLOC: aoc_common::heap::Alloc = aoc_common::heap::Alloc;`"
+    );
+    let usage = Usage {
+        peak_bytes: stats.max_bytes,
+        total_blocks: stats.total_blocks,
+    };
+    (value, usage)
+}
+
+/// Whether dhat sees the allocations of the process, i.e. [`Alloc`] is the global allocator.
+///
+/// # Panics
+///
+/// When another profiler is running.
+pub fn counts_allocations() -> bool {
+    let _profiler = dhat::Profiler::builder().testing().build();
+    drop(std::hint::black_box(Box::new(0u8)));
+    dhat::HeapStats::get().total_blocks > 0
+}
+
+/// Run `f`, the solution called `name`, and fail when it allocated more than `ceiling`.
+#[track_caller]
+pub fn assert_within<T>(name: &str, ceiling: Ceiling, f: impl FnOnce() -> T) -> T {
+    let (value, usage) = measure(f);
+    assert!(
+        usage.peak_bytes <= ceiling.peak_bytes && usage.total_blocks <= ceiling.total_blocks,
+        "{name} allocated {usage}, above its ceiling of {ceiling}"
+    );
+    value
+}
+
+/// A part of a day, as checked by [`check_day`].
+pub type Part = (&'static str, fn(&str) -> Result<String, AocError>, Ceiling);
+
+/// Run each part on `input`, and fail when one allocated more than its ceiling.
+#[track_caller]
+pub fn check_day(input: &str, parts: &[Part]) -> Result<(), AocError> {
+    for (name, process, ceiling) in parts {
+        assert_within(name, *ceiling, || process(input))?;
+    }
+    Ok(())
+}
+
+/// The heap test of a day, `tests/heap.rs`: the global allocator of its binary and a single
+/// test running [`check_day`] on the parts.
+///
+/// The ceilings hold on the generated input of the given size, `generate::input(SEED, size)`,
+/// not on the puzzle input, and are set about a quarter above what each part allocated on it.
+///
+/// ```ignore
+/// aoc_common::check_heap!(day_01, size: 1000, {
+///     // 4_112 bytes at the peak, 1_010 allocations when measured
+///     part1: { peak_bytes: 5_200, total_blocks: 1_300 },
+///     part2: { peak_bytes: 5_200, total_blocks: 1_300 },
+/// });
+/// ```
+#[macro_export]
+macro_rules! check_heap {
+    ($day:ident, size: $size:expr, {
+        $($part:ident: { peak_bytes: $peak_bytes:expr, total_blocks: $total_blocks:expr }),+ $(,)?
+    }) => {
+        #[global_allocator]
+        static ALLOC: $crate::heap::Alloc = $crate::heap::Alloc;
+
+        // a single test, dhat cannot profile two of them at once
+        #[test]
+        fn test_heap_usage() -> Result<(), $crate::AocError> {
+            let input = $day::generate::input($crate::generate::SEED, $size);
+            $crate::heap::check_day(
+                &input,
+                &[$((
+                    stringify!($part),
+                    $day::$part::process,
+                    $crate::heap::Ceiling {
+                        peak_bytes: $peak_bytes,
+                        total_blocks: $total_blocks,
+                    },
+                )),+],
+            )
+        }
+    };
+}
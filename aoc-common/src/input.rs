@@ -0,0 +1,74 @@
+use std::{
+    io::{ErrorKind, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::AocError;
+
+/// Environment variable pointing to a directory laid out as `day-XX/inputN.txt`.
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS_DIR";
+
+/// Path of the input of `part` of `day` within `inputs_dir`.
+pub fn input_path(inputs_dir: &Path, day: u8, part: u8) -> PathBuf {
+    inputs_dir
+        .join(format!("day-{day:02}"))
+        .join(format!("input{part}.txt"))
+}
+
+/// Read a puzzle input, a missing file is reported as [`AocError::MissingInput`].
+pub fn read(path: &Path) -> Result<String, AocError> {
+    std::fs::read_to_string(path).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => AocError::MissingInput {
+            path: path.to_path_buf(),
+        },
+        _ => e.into(),
+    })
+}
+
+/// Read a puzzle input piped through stdin.
+pub fn read_stdin() -> Result<String, AocError> {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+    Ok(input)
+}
+
+/// Input of `part` for the day crate at `manifest_dir`, used by benches.
+///
+/// Reads from [`INPUTS_DIR_VAR`] when it is set, next to the crate otherwise.
+pub fn day_input(manifest_dir: &str, part: u8) -> Result<String, AocError> {
+    let manifest_dir = Path::new(manifest_dir);
+    let path = match (std::env::var_os(INPUTS_DIR_VAR), manifest_dir.file_name()) {
+        (Some(inputs_dir), Some(day)) => Path::new(&inputs_dir).join(day),
+        _ => manifest_dir.to_path_buf(),
+    };
+    read(&path.join(format!("input{part}.txt")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_path() {
+        assert_eq!(
+            Path::new("inputs/day-05/input2.txt"),
+            input_path(Path::new("inputs"), 5, 2)
+        );
+    }
+
+    #[test]
+    fn test_read_missing() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("day-00/input1.txt");
+        match read(&path) {
+            Err(AocError::MissingInput { path: missing }) => assert_eq!(path, missing),
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_read() -> miette::Result<()> {
+        let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+        assert!(read(&manifest)?.contains("aoc-common"));
+        Ok(())
+    }
+}
@@ -0,0 +1,495 @@
+use std::fmt::Debug;
+
+use crate::AocError;
+
+/// Integers intervals and range maps are built on.
+pub trait Bound: Copy + Ord + Debug {
+    const MIN: Self;
+    const MAX: Self;
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_bound {
+    ($($t:ty),*) => {$(
+        impl Bound for $t {
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+        }
+    )*};
+}
+
+impl_bound!(u32, u64, i64);
+
+// arithmetic on values already known to fit, e.g. offsets within a validated segment
+fn add<T: Bound>(a: T, b: T) -> T {
+    a.checked_add(b)
+        .expect("value fits within a validated interval")
+}
+
+fn sub<T: Bound>(a: T, b: T) -> T {
+    a.checked_sub(b)
+        .expect("value fits within a validated interval")
+}
+
+/// The half-open interval `[start, end)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+impl<T: Bound> Interval<T> {
+    /// `[start, end)`, empty when `end` is not after `start`.
+    pub fn new(start: T, end: T) -> Self {
+        Self {
+            start,
+            end: end.max(start),
+        }
+    }
+
+    /// `[start, start + len)`, failing when the end does not fit in `T`.
+    pub fn from_len(start: T, len: T) -> Result<Self, AocError> {
+        match start.checked_add(len) {
+            Some(end) if end >= start => Ok(Self { start, end }),
+            Some(_) => Err(AocError::LogicError(format!(
+                "interval starting at {start:?} has a negative length {len:?}"
+            ))),
+            None => Err(AocError::Overflow(format!(
+                "the end of the interval starting at {start:?} of length {len:?}"
+            ))),
+        }
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    /// `None` when the length does not fit in `T`, e.g. `[i64::MIN, i64::MAX)`.
+    pub fn len(&self) -> Option<T> {
+        self.end.checked_sub(self.start)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        (start < end).then_some(Self { start, end })
+    }
+}
+
+/// Disjoint, sorted and non-adjacent intervals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Bound> IntervalSet<T> {
+    /// Normalize any intervals: sort them, merge those that overlap or touch, drop empty ones.
+    pub fn new(intervals: impl IntoIterator<Item = Interval<T>>) -> Self {
+        let mut intervals = intervals
+            .into_iter()
+            .filter(|interval| !interval.is_empty())
+            .collect::<Vec<_>>();
+        intervals.sort_unstable();
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        Self { intervals: merged }
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(Interval::start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| interval.contains(value))
+    }
+}
+
+impl<T: Bound> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
+
+/// A piece of a range map, shifting `source` so that it starts at `dest`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Segment<T> {
+    source: Interval<T>,
+    dest: T,
+}
+
+impl<T: Bound> Segment<T> {
+    /// Map `[source, source + len)` onto `[dest, dest + len)`, failing when either end overflows.
+    pub fn new(source: T, dest: T, len: T) -> Result<Self, AocError> {
+        Interval::from_len(dest, len)?;
+        Ok(Self {
+            source: Interval::from_len(source, len)?,
+            dest,
+        })
+    }
+
+    pub fn source(&self) -> Interval<T> {
+        self.source
+    }
+
+    pub fn image(&self) -> Interval<T> {
+        Interval::new(self.dest, self.shift(self.source.end))
+    }
+
+    // `value` must lie within the source, its end included
+    fn shift(&self, value: T) -> T {
+        add(self.dest, sub(value, self.source.start))
+    }
+
+    fn is_identity(&self) -> bool {
+        self.source.start == self.dest
+    }
+}
+
+/// A piecewise translation: values within a segment are shifted, all others map to themselves.
+///
+/// The domain is `[T::MIN, T::MAX)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeMap<T> {
+    // sorted, disjoint, without identity segments and with contiguous ones merged
+    segments: Vec<Segment<T>>,
+}
+
+impl<T: Bound> Default for RangeMap<T> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<T: Bound> RangeMap<T> {
+    pub fn identity() -> Self {
+        Self {
+            segments: Vec::new(),
+        }
+    }
+
+    /// Build a map from segments in any order, failing when their sources overlap.
+    pub fn new(segments: impl IntoIterator<Item = Segment<T>>) -> Result<Self, AocError> {
+        let mut segments = segments
+            .into_iter()
+            .filter(|segment| !segment.source.is_empty())
+            .collect::<Vec<_>>();
+        segments.sort_unstable();
+        if let Some(pair) = segments
+            .windows(2)
+            .find(|pair| pair[1].source.start < pair[0].source.end)
+        {
+            return Err(AocError::LogicError(format!(
+                "range map segments overlap: {:?} and {:?}",
+                pair[0].source, pair[1].source
+            )));
+        }
+        Ok(Self::normalized(segments))
+    }
+
+    // `segments` must be sorted and disjoint
+    fn normalized(segments: Vec<Segment<T>>) -> Self {
+        let mut merged: Vec<Segment<T>> = Vec::with_capacity(segments.len());
+        for segment in segments {
+            if segment.is_identity() || segment.source.is_empty() {
+                continue;
+            }
+            match merged.last_mut() {
+                Some(last)
+                    if last.source.end == segment.source.start
+                        && last.shift(last.source.end) == segment.dest =>
+                {
+                    last.source.end = segment.source.end;
+                }
+                _ => merged.push(segment),
+            }
+        }
+        Self { segments: merged }
+    }
+
+    pub fn segments(&self) -> &[Segment<T>] {
+        &self.segments
+    }
+
+    pub fn get(&self, value: T) -> T {
+        let index = self.segments.partition_point(|s| s.source.end <= value);
+        match self.segments.get(index) {
+            Some(segment) if segment.source.contains(value) => segment.shift(value),
+            _ => value,
+        }
+    }
+
+    /// Cut `interval` along the segments, pairing each piece with its image.
+    pub fn split(&self, interval: Interval<T>) -> Vec<(Interval<T>, Interval<T>)> {
+        let mut pieces = Vec::new();
+        let mut cursor = interval.start;
+        let first = self
+            .segments
+            .partition_point(|s| s.source.end <= interval.start);
+        for segment in &self.segments[first..] {
+            let Some(part) = segment.source.intersection(&interval) else {
+                break;
+            };
+            if cursor < part.start {
+                let gap = Interval::new(cursor, part.start);
+                pieces.push((gap, gap));
+            }
+            let image = Interval::new(segment.shift(part.start), segment.shift(part.end));
+            pieces.push((part, image));
+            cursor = part.end;
+        }
+        if cursor < interval.end {
+            let rest = Interval::new(cursor, interval.end);
+            pieces.push((rest, rest));
+        }
+        pieces
+    }
+
+    /// Map every value of `set`.
+    pub fn apply(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        set.intervals()
+            .iter()
+            .flat_map(|interval| self.split(*interval))
+            .map(|(_, image)| image)
+            .collect()
+    }
+
+    /// A single map equivalent to applying `self` and then `next`.
+    pub fn then(&self, next: &RangeMap<T>) -> RangeMap<T> {
+        let mut segments = Vec::new();
+        for (source, image) in self.split(Interval::new(T::MIN, T::MAX)) {
+            for (image_part, next_image) in next.split(image) {
+                let source_part = if source.start == image.start {
+                    image_part
+                } else {
+                    // within one of our segments, so the offsets fit
+                    Interval::new(
+                        add(source.start, sub(image_part.start, image.start)),
+                        add(source.start, sub(image_part.end, image.start)),
+                    )
+                };
+                segments.push(Segment {
+                    source: source_part,
+                    dest: next_image.start,
+                });
+            }
+        }
+        Self::normalized(segments)
+    }
+
+    /// The map undoing this one, which only exists when it is a bijection.
+    pub fn invert(&self) -> Result<RangeMap<T>, AocError> {
+        let mut images = self.segments.iter().map(Segment::image).collect::<Vec<_>>();
+        images.sort_unstable();
+        let disjoint = images.windows(2).all(|pair| pair[0].end <= pair[1].start);
+        let sources = IntervalSet::new(self.segments.iter().map(Segment::source));
+        if !disjoint || IntervalSet::new(images) != sources {
+            return Err(AocError::LogicError(
+                "range map is not a bijection and cannot be inverted".to_string(),
+            ));
+        }
+        Self::new(self.segments.iter().map(|segment| Segment {
+            source: segment.image(),
+            dest: segment.source.start,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn interval(start: u32, len: u32) -> Interval<u32> {
+        Interval::from_len(start, len).unwrap()
+    }
+
+    fn map(segments: &[(u64, u64, u64)]) -> RangeMap<u64> {
+        RangeMap::new(
+            segments
+                .iter()
+                .map(|&(source, dest, len)| Segment::new(source, dest, len).unwrap()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_interval_overflow() {
+        assert!(matches!(
+            Interval::from_len(4_000_000_000u32, 500_000_000),
+            Err(AocError::Overflow(_))
+        ));
+        assert!(Interval::from_len(u32::MAX - 1, 1).is_ok());
+        assert!(matches!(
+            Interval::from_len(5i64, -1),
+            Err(AocError::LogicError(_))
+        ));
+        assert!(Segment::new(0u32, u32::MAX, 2).is_err());
+    }
+
+    #[rstest]
+    #[case(interval(0, 10), interval(5, 10), Some(interval(5, 5)))]
+    #[case(interval(0, 10), interval(10, 10), None)]
+    #[case(interval(5, 2), interval(0, 10), Some(interval(5, 2)))]
+    fn test_intersection(
+        #[case] a: Interval<u32>,
+        #[case] b: Interval<u32>,
+        #[case] expected: Option<Interval<u32>>,
+    ) {
+        assert_eq!(expected, a.intersection(&b));
+        assert_eq!(expected, b.intersection(&a));
+    }
+
+    #[test]
+    fn test_interval_set_normalizes() {
+        let set = IntervalSet::new([
+            interval(20, 5),
+            interval(0, 10),
+            interval(5, 10),
+            interval(25, 1),
+            interval(40, 0),
+        ]);
+        assert_eq!(&[interval(0, 15), interval(20, 6)], set.intervals());
+        assert_eq!(Some(0), set.min());
+        assert!(set.contains(25));
+        assert!(!set.contains(26));
+    }
+
+    #[rstest]
+    // disjoint
+    #[case(
+        &[(0, 100, 10), (20, 200, 10)],
+        (0, 30),
+        &[(10, 10), (100, 10), (200, 10)]
+    )]
+    // no overlap
+    #[case(&[(0, 100, 10), (20, 200, 10)], (100, 30), &[(100, 30)])]
+    // everything!
+    #[case(
+        &[(10, 100, 10), (30, 200, 10)],
+        (0, 50),
+        &[(0, 10), (20, 10), (40, 10), (100, 10), (200, 10)]
+    )]
+    // shifted onto its own neighbourhood
+    #[case(&[(98, 50, 2), (50, 52, 48)], (79, 14), &[(81, 14)])]
+    fn test_apply(
+        #[case] segments: &[(u64, u64, u64)],
+        #[case] range: (u64, u64),
+        #[case] expected: &[(u64, u64)],
+    ) {
+        let set = IntervalSet::new([Interval::from_len(range.0, range.1).unwrap()]);
+        let expected = IntervalSet::new(
+            expected
+                .iter()
+                .map(|&(start, len)| Interval::from_len(start, len).unwrap()),
+        );
+        assert_eq!(expected, map(segments).apply(&set));
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(9, 9)]
+    #[case(10, 100)]
+    #[case(19, 109)]
+    #[case(20, 20)]
+    #[case(35, 205)]
+    fn test_get(#[case] value: u64, #[case] expected: u64) {
+        assert_eq!(expected, map(&[(10, 100, 10), (30, 200, 10)]).get(value));
+    }
+
+    #[test]
+    fn test_new_rejects_overlaps_and_normalizes() {
+        let segments = [(10, 20, 10), (15, 0, 10)].map(|(s, d, l)| Segment::new(s, d, l).unwrap());
+        assert!(RangeMap::<u64>::new(segments).is_err());
+
+        // contiguous segments shifting by the same amount merge, identity ones vanish
+        let merged = map(&[(10, 110, 5), (15, 115, 5), (30, 30, 10)]);
+        assert_eq!(&[Segment::new(10, 110, 10).unwrap()], merged.segments());
+    }
+
+    #[test]
+    fn test_then() {
+        let first = map(&[(98, 50, 2), (50, 52, 48)]);
+        let second = map(&[(15, 0, 37), (52, 37, 2), (0, 39, 15)]);
+        let composed = first.then(&second);
+        for value in 0..200 {
+            assert_eq!(second.get(first.get(value)), composed.get(value), "{value}");
+        }
+        assert_eq!(
+            RangeMap::identity(),
+            RangeMap::<u64>::identity().then(&RangeMap::identity())
+        );
+    }
+
+    #[test]
+    fn test_then_signed() {
+        let first = RangeMap::new([Segment::new(-10i64, 10, 5).unwrap()]).unwrap();
+        let second = RangeMap::new([Segment::new(i64::MIN, 0, 3).unwrap()]).unwrap();
+        let composed = first.then(&second);
+        for value in [
+            i64::MIN,
+            i64::MIN + 2,
+            i64::MIN + 3,
+            -10,
+            -6,
+            -5,
+            0,
+            i64::MAX - 1,
+        ] {
+            assert_eq!(second.get(first.get(value)), composed.get(value), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_invert() -> miette::Result<()> {
+        // swaps [0, 10) and [10, 20)
+        let swap = map(&[(0, 10, 10), (10, 0, 10)]);
+        let inverse = swap.invert()?;
+        for value in 0..30 {
+            assert_eq!(value, inverse.get(swap.get(value)));
+        }
+        assert_eq!(RangeMap::identity(), swap.then(&inverse));
+
+        // [0, 10) lands on [100, 110) which keeps mapping to itself
+        assert!(map(&[(0, 100, 10)]).invert().is_err());
+        // both segments land on [10, 20)
+        assert!(map(&[(0, 10, 10), (20, 10, 10)]).invert().is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,14 @@
+pub mod error;
+pub mod generate;
+pub mod grid;
+#[cfg(feature = "dhat-heap")]
+pub mod heap;
+pub mod input;
+pub mod interval;
+pub mod solution;
+pub mod trace;
+
+pub use error::{AocError, Location, ParseError, Positioned};
+pub use grid::{Grid, Offset, Pos};
+pub use interval::{Interval, IntervalSet, RangeMap, Segment};
+pub use solution::{Registry, Selector, Solution, SolutionId, Solver};
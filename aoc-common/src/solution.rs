@@ -0,0 +1,201 @@
+use std::fmt::Display;
+
+use crate::AocError;
+
+/// One part of one day's puzzle.
+pub trait Solution: Sync {
+    fn year(&self) -> u16;
+    fn day(&self) -> u8;
+    fn part(&self) -> u8;
+    fn solve(&self, input: &str) -> Result<String, AocError>;
+
+    fn id(&self) -> SolutionId {
+        SolutionId {
+            year: self.year(),
+            day: self.day(),
+            part: self.part(),
+        }
+    }
+}
+
+/// Identifies a solution, e.g. `2023 day-05 part2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SolutionId {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+}
+
+impl Display for SolutionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day-{:02} part{}", self.year, self.day, self.part)
+    }
+}
+
+/// A solution backed by a day's `process` function.
+pub struct Solver {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub process: fn(&str) -> Result<String, AocError>,
+}
+
+impl Solution for Solver {
+    fn year(&self) -> u16 {
+        self.year
+    }
+
+    fn day(&self) -> u8 {
+        self.day
+    }
+
+    fn part(&self) -> u8 {
+        self.part
+    }
+
+    fn solve(&self, input: &str) -> Result<String, AocError> {
+        (self.process)(input)
+    }
+}
+
+/// Declare the `SOLUTIONS` of a day crate from its `process` functions.
+///
+/// ```ignore
+/// aoc_common::solutions! {
+///     year: 2023,
+///     day: 5,
+///     1 => part1::process,
+///     2 => part2::process,
+/// }
+/// ```
+#[macro_export]
+macro_rules! solutions {
+    (year: $year:expr, day: $day:expr, $($part:literal => $process:path),+ $(,)?) => {
+        pub static SOLUTIONS: &[&dyn $crate::Solution] = &[
+            $(&$crate::Solver {
+                year: $year,
+                day: $day,
+                part: $part,
+                process: $process,
+            }),+
+        ];
+    };
+}
+
+/// Narrows down the solutions of a registry, `None` matches anything.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Selector {
+    pub year: Option<u16>,
+    pub day: Option<u8>,
+    pub part: Option<u8>,
+}
+
+impl Selector {
+    pub fn matches(&self, id: &SolutionId) -> bool {
+        self.year.is_none_or(|year| year == id.year)
+            && self.day.is_none_or(|day| day == id.day)
+            && self.part.is_none_or(|part| part == id.part)
+    }
+}
+
+/// Every solution a runner knows about, ordered by year, day and part.
+#[derive(Default)]
+pub struct Registry {
+    solutions: Vec<&'static dyn Solution>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, solutions: &[&'static dyn Solution]) -> Self {
+        self.solutions.extend_from_slice(solutions);
+        self.solutions.sort_by_key(|solution| solution.id());
+        self
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'static dyn Solution> + '_ {
+        self.solutions.iter().copied()
+    }
+
+    pub fn select(&self, selector: Selector) -> impl Iterator<Item = &'static dyn Solution> + '_ {
+        self.iter()
+            .filter(move |solution| selector.matches(&solution.id()))
+    }
+
+    pub fn get(&self, id: SolutionId) -> Option<&'static dyn Solution> {
+        self.iter().find(|solution| solution.id() == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn answer(input: &str) -> Result<String, AocError> {
+        Ok(input.len().to_string())
+    }
+
+    mod day_02 {
+        crate::solutions! {
+            year: 2023,
+            day: 2,
+            1 => super::answer,
+            2 => super::answer,
+        }
+    }
+
+    mod day_01 {
+        crate::solutions! {
+            year: 2024,
+            day: 1,
+            1 => super::answer,
+        }
+    }
+
+    fn registry() -> Registry {
+        Registry::new()
+            .with(day_01::SOLUTIONS)
+            .with(day_02::SOLUTIONS)
+    }
+
+    #[test]
+    fn test_registry_is_ordered() {
+        let ids = registry()
+            .iter()
+            .map(|solution| solution.id().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "2023 day-02 part1",
+                "2023 day-02 part2",
+                "2024 day-01 part1"
+            ],
+            ids
+        );
+    }
+
+    #[rstest]
+    #[case(Selector::default(), 3)]
+    #[case(Selector { year: Some(2023), ..Default::default() }, 2)]
+    #[case(Selector { day: Some(1), ..Default::default() }, 1)]
+    #[case(Selector { part: Some(1), ..Default::default() }, 2)]
+    #[case(Selector { year: Some(2024), day: Some(2), part: None }, 0)]
+    fn test_select(#[case] selector: Selector, #[case] expected: usize) {
+        assert_eq!(expected, registry().select(selector).count());
+    }
+
+    #[test]
+    fn test_solve() -> miette::Result<()> {
+        let id = SolutionId {
+            year: 2023,
+            day: 2,
+            part: 2,
+        };
+        let solution = registry().get(id).expect("solution is registered");
+        assert_eq!("5", solution.solve("hello")?);
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+//! Subscriber of the `tracing` spans the solutions are instrumented with, and their policy.
+//!
+//! - an instrumented function skips the arguments that hold the input or what was parsed from
+//!   it, recording the input through [`detail`] instead: `#[tracing::instrument(skip(input),
+//!   fields(input = detail(input)))]`, small arguments are recorded as usual
+//! - every part is instrumented as `process`, `parse` and `solve`, for the timeline of a run to
+//!   show its stages
+//! - a function called once per step of a loop, like the visitor of a walk, only gets a span with
+//!   the `detailed` feature, at trace level and without arguments: `#[cfg_attr(feature =
+//!   "detailed", tracing::instrument(level = "trace", skip_all))]`
+//! - the `detailed` feature makes [`detail`] record those arguments, to debug a solution
+//! - the `untraced` feature compiles every span and event out, for benches of the solutions alone
+
+use std::{fmt::Debug, fs::File};
+
+use tracing::{field::DebugValue, Subscriber};
+use tracing_chrome::{ChromeLayer, ChromeLayerBuilder};
+use tracing_subscriber::{
+    fmt, layer::SubscriberExt, registry::LookupSpan, util::SubscriberInitExt, EnvFilter, Layer,
+};
+
+pub use tracing_chrome::FlushGuard;
+
+/// Log the events selected by `RUST_LOG` to stderr.
+///
+/// Every span is also written to `chrome_trace` when given, as Chrome trace events to open in
+/// Perfetto, the file being complete once the returned guard is dropped.
+/// Built with the `tracy` feature, every span is also streamed to the Tracy profiler as a zone.
+/// Both see the spans whatever `RUST_LOG` selects.
+pub fn init(chrome_trace: Option<File>) -> Option<FlushGuard> {
+    let (chrome, guard) = chrome_trace.map(chrome_layer).unzip();
+    let registry = tracing_subscriber::registry()
+        .with(fmt::layer().with_filter(EnvFilter::from_default_env()))
+        .with(chrome);
+    #[cfg(feature = "tracy")]
+    let registry = registry.with(tracing_tracy::TracyLayer::default());
+    registry.init();
+    guard
+}
+
+// each span as a pair of begin and end events, with its fields as arguments
+fn chrome_layer<S>(file: File) -> (ChromeLayer<S>, FlushGuard)
+where
+    S: Subscriber + for<'a> LookupSpan<'a> + Send + Sync,
+{
+    ChromeLayerBuilder::new()
+        .writer(file)
+        .include_args(true)
+        .build()
+}
+
+/// A large argument as a span field, only recorded with the `detailed` feature.
+///
+/// Like any field, it is only formatted when a subscriber is interested in the span.
+pub fn detail<T: Debug>(value: T) -> Option<DebugValue<T>> {
+    cfg!(feature = "detailed").then(|| tracing::field::debug(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miette::IntoDiagnostic;
+
+    #[test]
+    fn test_chrome_layer() -> miette::Result<()> {
+        let dir = tempfile::tempdir().into_diagnostic()?;
+        let path = dir.path().join("trace.json");
+        let (layer, guard) = chrome_layer(File::create(&path).into_diagnostic()?);
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(layer), || {
+            tracing::info_span!("solve", id = "2023 day-05 part1")
+                .in_scope(|| tracing::info_span!("parse").in_scope(|| {}));
+        });
+        drop(guard);
+
+        let trace = std::fs::read_to_string(&path).into_diagnostic()?;
+        let names = [
+            "\"name\":\"solve\"",
+            "\"name\":\"parse\"",
+            "2023 day-05 part1",
+        ];
+        for name in names {
+            assert!(trace.contains(name), "{name} missing from {trace}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_detail() {
+        assert_eq!(cfg!(feature = "detailed"), detail("input").is_some());
+    }
+}
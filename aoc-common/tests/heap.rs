@@ -0,0 +1,36 @@
+use aoc_common::heap::{self, Ceiling};
+
+#[global_allocator]
+static ALLOC: heap::Alloc = heap::Alloc;
+
+// a single test, dhat cannot profile two of them at once
+#[test]
+fn test_assert_within() {
+    let (len, usage) = heap::measure(|| vec![0u8; 4096].len());
+    assert_eq!(4096, len);
+    assert!(usage.peak_bytes >= 4096, "{usage}");
+    assert_eq!(1, usage.total_blocks);
+    assert!(heap::counts_allocations());
+    let ((), usage) = heap::measure(|| ());
+    assert_eq!(0, usage.total_blocks);
+
+    let ceiling = Ceiling {
+        peak_bytes: 1024,
+        total_blocks: 10,
+    };
+    assert_eq!(
+        3,
+        heap::assert_within("small", ceiling, || vec![1, 2, 3].len())
+    );
+    let exceeded = std::panic::catch_unwind(|| {
+        heap::assert_within("large", ceiling, || vec![0u8; 4096].len())
+    })
+    .expect_err("4096 bytes are above the ceiling");
+    let message = exceeded
+        .downcast_ref::<String>()
+        .expect("a formatted message");
+    assert!(
+        message.starts_with("large allocated 4096 bytes at the peak, 1 allocations"),
+        "{message}"
+    );
+}
@@ -0,0 +1,100 @@
+use std::{collections::BTreeMap, path::Path};
+
+use miette::{Context, IntoDiagnostic};
+use serde::{Deserialize, Serialize};
+
+/// Name of the answers file, stored next to the inputs of a day.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Accepted answers of a day, one table per input file:
+///
+/// ```toml
+/// [input1]
+/// part1 = "56042"
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, BTreeMap<String, String>>);
+
+impl Answers {
+    /// Load the answers at `path`, a missing file holds no answers.
+    pub fn load(path: &Path) -> miette::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content)
+                .wrap_err_with(|| format!("invalid answers file `{}`", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).into_diagnostic(),
+        }
+    }
+
+    pub fn parse(content: &str) -> miette::Result<Self> {
+        toml::from_str(content).into_diagnostic()
+    }
+
+    pub fn save(&self, path: &Path) -> miette::Result<()> {
+        let content = toml::to_string(self).into_diagnostic()?;
+        std::fs::write(path, content)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("write answers file `{}`", path.display()))
+    }
+
+    pub fn get(&self, input: &str, part: u8) -> Option<&str> {
+        self.0
+            .get(input)
+            .and_then(|answers| answers.get(&format!("part{part}")))
+            .map(String::as_str)
+    }
+
+    pub fn set(&mut self, input: &str, part: u8, answer: impl Into<String>) {
+        self.0
+            .entry(input.to_string())
+            .or_default()
+            .insert(format!("part{part}"), answer.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = r#"[input1]
+part1 = "56042"
+
+[input2]
+part1 = "56042"
+part2 = "55358"
+"#;
+
+    #[test]
+    fn test_get() -> miette::Result<()> {
+        let answers = Answers::parse(ANSWERS)?;
+        assert_eq!(Some("56042"), answers.get("input1", 1));
+        assert_eq!(Some("55358"), answers.get("input2", 2));
+        assert_eq!(None, answers.get("input1", 2));
+        assert_eq!(None, answers.get("input3", 1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_and_load() -> miette::Result<()> {
+        let dir = tempfile::tempdir().into_diagnostic()?;
+        let path = dir.path().join(ANSWERS_FILE);
+        assert_eq!(Answers::default(), Answers::load(&path)?);
+
+        let mut answers = Answers::default();
+        answers.set("input1", 1, "56042");
+        answers.set("input2", 2, "55358");
+        answers.set("input2", 1, "56042");
+        answers.save(&path)?;
+
+        assert_eq!(ANSWERS, std::fs::read_to_string(&path).into_diagnostic()?);
+        assert_eq!(answers, Answers::load(&path)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_invalid() {
+        let err = Answers::parse("[input1]\npart1 = 12").unwrap_err();
+        assert!(err.to_string().contains("invalid type"));
+    }
+}
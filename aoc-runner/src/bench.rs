@@ -0,0 +1,578 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    path::Path,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use miette::{Context, IntoDiagnostic};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{digit1, space0, space1},
+    combinator::{map_res, value},
+    number::complete::double,
+    sequence::{delimited, preceded, terminated, tuple},
+    IResult,
+};
+use serde::{Deserialize, Serialize};
+
+/// Name of the benchmark history, stored in the year directory.
+pub const BENCH_HISTORY_FILE: &str = "bench-history.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Tool {
+    Divan,
+    Criterion,
+}
+
+impl Display for Tool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tool::Divan => write!(f, "divan"),
+            Tool::Criterion => write!(f, "criterion"),
+        }
+    }
+}
+
+/// A benchmark across runs, the times of different tools are not comparable.
+pub type Key<'a> = (u8, &'a str, Tool);
+
+/// The result of one benchmark, times in nanoseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub day: u8,
+    /// Path of the benchmark within its harness, e.g. `part2/while`.
+    pub bench: String,
+    pub part: Option<u8>,
+    pub tool: Tool,
+    pub fastest: Option<f64>,
+    pub median: Option<f64>,
+    pub mean: Option<f64>,
+    pub samples: Option<u64>,
+    #[serde(flatten)]
+    pub setup: Setup,
+}
+
+impl Record {
+    /// The time trends and comparisons are based on: the median, or the mean when there is none.
+    pub fn typical(&self) -> Option<f64> {
+        self.median.or(self.mean)
+    }
+
+    fn key(&self) -> Key<'_> {
+        (self.day, &self.bench, self.tool)
+    }
+}
+
+// the trimmed standard output of a successful command
+fn output(program: &str, args: &[&str]) -> Option<String> {
+    Command::new(program)
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|output| !output.is_empty())
+}
+
+/// The full hash of a git revision such as `HEAD~2`, or `revision` itself when git does not
+/// know it, e.g. a commit recorded as `-dirty`.
+pub fn resolve_commit(revision: &str) -> String {
+    output(
+        "git",
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{revision}^{{commit}}"),
+        ],
+    )
+    .unwrap_or_else(|| revision.to_string())
+}
+
+/// Where and when benchmarks were run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Setup {
+    pub commit: String,
+    pub rustc: String,
+    pub cpu: String,
+    /// Seconds since the Unix epoch.
+    pub at: u64,
+}
+
+impl Setup {
+    /// Describe the current checkout, toolchain and machine.
+    pub fn current() -> Self {
+        let mut commit = output("git", &["rev-parse", "--short", "HEAD"])
+            .unwrap_or_else(|| "unknown".to_string());
+        if output("git", &["status", "--porcelain", "--untracked-files=no"]).is_some() {
+            commit.push_str("-dirty");
+        }
+        let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+        let cpu = std::fs::read_to_string("/proc/cpuinfo")
+            .ok()
+            .and_then(|info| {
+                info.lines()
+                    .find_map(|line| line.strip_prefix("model name"))
+                    .and_then(|line| line.split_once(':'))
+                    .map(|(_, name)| name.trim().to_string())
+            })
+            .or_else(|| output("sysctl", &["-n", "machdep.cpu.brand_string"]));
+        Self {
+            commit,
+            rustc: output(&rustc, &["--version"]).unwrap_or_else(|| "unknown".to_string()),
+            cpu: cpu.unwrap_or_else(|| "unknown".to_string()),
+            at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+        }
+    }
+}
+
+/// Every recorded benchmark of a year, oldest first:
+///
+/// ```toml
+/// [[bench]]
+/// day = 1
+/// bench = "part1"
+/// part = 1
+/// tool = "divan"
+/// fastest = 38370.0
+/// median = 39040.0
+/// mean = 39920.0
+/// samples = 100
+/// commit = "56e8c58"
+/// rustc = "rustc 1.85.0-nightly"
+/// cpu = "Apple M1"
+/// at = 1701406800
+/// ```
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default, rename = "bench")]
+    pub records: Vec<Record>,
+}
+
+impl History {
+    /// Load the history at `path`, a missing file holds no records.
+    pub fn load(path: &Path) -> miette::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("invalid bench history `{}`", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).into_diagnostic(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> miette::Result<()> {
+        let content = toml::to_string(self).into_diagnostic()?;
+        std::fs::write(path, content)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("write bench history `{}`", path.display()))
+    }
+
+    // the last record of each benchmark run at `commit`, a prefix or the full hash of
+    // the recorded one
+    fn at_commit(&self, commit: &str) -> BTreeMap<Key<'_>, &Record> {
+        self.records
+            .iter()
+            .filter(|record| {
+                let recorded = &record.setup.commit;
+                recorded.starts_with(commit) || commit.starts_with(recorded.as_str())
+            })
+            .map(|record| (record.key(), record))
+            .collect()
+    }
+
+    /// The records of each benchmark, oldest first, optionally only those of `day`.
+    pub fn trends(&self, day: Option<u8>) -> BTreeMap<Key<'_>, Vec<&Record>> {
+        let mut trends: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for record in &self.records {
+            if day.is_none_or(|day| day == record.day) {
+                trends.entry(record.key()).or_default().push(record);
+            }
+        }
+        trends
+    }
+
+    /// Compare the benchmarks run at both commits, flagging those slower by more than
+    /// `threshold` percent.
+    pub fn compare(&self, base: &str, head: &str, threshold: f64) -> Vec<Comparison> {
+        let base = self.at_commit(base);
+        let head = self.at_commit(head);
+        base.iter()
+            .filter_map(|(key, base)| {
+                let (base_time, head_time) = (base.typical()?, head.get(key)?.typical()?);
+                let change = (head_time - base_time) / base_time * 100.0;
+                Some(Comparison {
+                    day: key.0,
+                    bench: key.1.to_string(),
+                    tool: key.2,
+                    base: base_time,
+                    head: head_time,
+                    change,
+                    regression: change > threshold,
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub bench: String,
+    pub tool: Tool,
+    pub base: f64,
+    pub head: f64,
+    /// In percent, positive when slower.
+    pub change: f64,
+    pub regression: bool,
+}
+
+/// A time in nanoseconds, e.g. `38.37 µs`.
+pub struct Nanos(pub f64);
+
+impl Display for Nanos {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (value, unit) = match self.0 {
+            ns if ns >= 1e9 => (ns / 1e9, "s"),
+            ns if ns >= 1e6 => (ns / 1e6, "ms"),
+            ns if ns >= 1e3 => (ns / 1e3, "µs"),
+            ns => (ns, "ns"),
+        };
+        f.pad(&format!("{value:.2} {unit}"))
+    }
+}
+
+// a time as printed by divan or criterion, in nanoseconds
+fn parse_time(input: &str) -> IResult<&str, f64> {
+    let (input, number) = terminated(double, space1)(input)?;
+    let (input, scale) = alt((
+        value(1e-3, tag("ps")),
+        value(1.0, tag("ns")),
+        value(1e3, tag("µs")),
+        value(1e3, tag("us")),
+        value(1e6, tag("ms")),
+        value(1e9, tag("s")),
+    ))(input)?;
+    // to the picosecond, dropping the noise of the scaling
+    Ok((input, (number * scale * 1e3).round() / 1e3))
+}
+
+// criterion's `[low estimate high]`
+fn parse_estimates(input: &str) -> IResult<&str, (f64, f64, f64)> {
+    delimited(
+        tuple((space0, tag("["))),
+        tuple((
+            parse_time,
+            preceded(space1, parse_time),
+            preceded(space1, parse_time),
+        )),
+        tag("]"),
+    )(input)
+}
+
+// `day_05_bench` or `day_05::part1`
+fn parse_day(input: &str) -> IResult<&str, u8> {
+    preceded(tag("day_"), map_res(digit1, str::parse))(input)
+}
+
+fn part_of(bench: &str) -> Option<u8> {
+    bench.split('/').next()?.strip_prefix("part")?.parse().ok()
+}
+
+// a cell of a divan row, empty for a group
+fn parse_cell<'a, T>(
+    cell: &'a str,
+    parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> Option<T> {
+    delimited(space0, parser, space0)(cell)
+        .ok()
+        .map(|(_, value)| value)
+}
+
+/// Read the benchmarks out of divan and criterion output.
+pub fn parse_output(output: &str, setup: &Setup) -> Vec<Record> {
+    let mut records = Vec::new();
+    // divan: the day of the current table and the names of the groups the row is nested in
+    let mut table: Option<u8> = None;
+    let mut groups: Vec<String> = Vec::new();
+    // criterion: a name too long for the line of its results comes on its own line before them
+    let mut previous_line = "";
+
+    for line in output.lines() {
+        if let Ok((_, day)) = terminated(parse_day, tag("_bench"))(line) {
+            table = line.contains("fastest").then_some(day);
+            groups.clear();
+        } else if let Some(day) = table {
+            // rows are drawn as a tree, `├─ name` nested three columns deeper per level
+            let start = line.find(|c: char| c.is_alphanumeric() || c == '_');
+            let Some(start) = start.filter(|start| *start > 0) else {
+                table = None;
+                continue;
+            };
+            let (name, cells) = line[start..]
+                .split_once(' ')
+                .unwrap_or((&line[start..], ""));
+            let depth = line[..start].chars().count() / 3;
+            groups.truncate(depth.saturating_sub(1));
+            groups.push(name.to_string());
+
+            let cells = cells.split('│').collect::<Vec<_>>();
+            let time = |i: usize| cells.get(i).and_then(|cell| parse_cell(cell, parse_time));
+            let Some(median) = time(2) else {
+                continue;
+            };
+            let bench = groups.join("/");
+            records.push(Record {
+                day,
+                part: part_of(&bench),
+                bench,
+                tool: Tool::Divan,
+                fastest: time(0),
+                median: Some(median),
+                mean: time(3),
+                samples: cells
+                    .get(4)
+                    .and_then(|cell| parse_cell(cell, nom::character::complete::u64)),
+                setup: setup.clone(),
+            });
+        } else if let Some((name, results)) = line.split_once("time:") {
+            let name = match name.trim() {
+                "" => previous_line.trim(),
+                name => name,
+            };
+            let Ok((id, day)) = terminated(parse_day, tag("::"))(name) else {
+                continue;
+            };
+            let Ok((_, (_, estimate, _))) = parse_estimates(results) else {
+                continue;
+            };
+            // `part1/part1` for a benchmark named after its group
+            let mut components = id.split('/').collect::<Vec<_>>();
+            components.dedup();
+            let bench = components.join("/");
+            records.push(Record {
+                day,
+                part: part_of(&bench),
+                bench,
+                tool: Tool::Criterion,
+                fastest: None,
+                median: None,
+                mean: Some(estimate),
+                samples: None,
+                setup: setup.clone(),
+            });
+        } else if let Some(record) = records.last_mut().filter(|r| r.tool == Tool::Criterion) {
+            // `Found 14 outliers among 100 measurements (14.00%)`
+            let measurements = line
+                .split_once("among ")
+                .and_then(|(_, rest)| nom::character::complete::u64::<_, ()>(rest).ok());
+            if let Some((_, samples)) = measurements {
+                record.samples.get_or_insert(samples);
+            }
+        }
+        previous_line = line;
+    }
+    records
+}
+
+/// Render the records of each benchmark with the change from one run to the next.
+pub fn render_trends(trends: &BTreeMap<Key<'_>, Vec<&Record>>) -> String {
+    let mut output = String::new();
+    for ((day, bench, tool), records) in trends {
+        output.push_str(&format!("day-{day:02} {bench} ({tool})\n"));
+        let mut previous: Option<f64> = None;
+        for record in records {
+            let Some(time) = record.typical() else {
+                continue;
+            };
+            let change = previous
+                .map(|previous| format!("{:+.2}%", (time - previous) / previous * 100.0))
+                .unwrap_or_default();
+            output.push_str(&format!(
+                "  {:<14} {:>12}  {:>8}  {}\n",
+                record.setup.commit,
+                Nanos(time),
+                change,
+                record.setup.rustc
+            ));
+            previous = Some(time);
+        }
+    }
+    output
+}
+
+pub fn render_comparisons(comparisons: &[Comparison]) -> String {
+    comparisons
+        .iter()
+        .map(|c| {
+            format!(
+                "day-{:02} {:<20} {:<9} {:>12} -> {:>12}  {:>+8.2}%{}\n",
+                c.day,
+                c.bench,
+                c.tool,
+                Nanos(c.base),
+                Nanos(c.head),
+                c.change,
+                if c.regression { "  regression" } else { "" }
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const DIVAN: &str = "
+running 8 tests
+iiiiiiii
+test result: ok. 0 passed; 0 failed; 8 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+day_03_bench     fastest       │ slowest       │ median        │ mean          │ samples │ iters
+├─ part1         27.7 µs       │ 56.58 µs      │ 27.91 µs      │ 29.06 µs      │ 100     │ 100
+╰─ part2                       │               │               │               │         │
+   ├─ many_till  114.7 µs      │ 154.2 µs      │ 123.3 µs      │ 122.9 µs      │ 100     │ 100
+   ╰─ while      55.87 µs      │ 83.37 µs      │ 62.77 µs      │ 64.54 µs      │ 100     │ 100
+
+day_04_bench  fastest       │ slowest       │ median        │ mean          │ samples │ iters
+╰─ part1      2.02 ms       │ 2.418 ms      │ 2.106 ms      │ 2.128 ms      │ 100     │ 100
+";
+
+    const CRITERION: &str = "day_01::part1/part1     time:   [268.09 µs 270.28 µs 273.08 µs]
+                        change: [-9.6460% -6.2513% -3.2074%] (p = 0.00 < 0.05)
+                        Performance has improved.
+Found 14 outliers among 100 measurements (14.00%)
+  6 (6.00%) high mild
+
+day_01::part2/a_rather_long_benchmark_name
+                        time:   [1.5581 ms 1.5616 ms 1.5657 ms]
+";
+
+    fn setup(commit: &str) -> Setup {
+        Setup {
+            commit: commit.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    #[case("27.7 µs", 27_700.0)]
+    #[case("2.106 ms", 2_106_000.0)]
+    #[case("950 ns", 950.0)]
+    #[case("1.5 s", 1_500_000_000.0)]
+    fn test_parse_time(#[case] input: &str, #[case] expected: f64) {
+        let (_, time) = parse_time(input).unwrap();
+        assert!((time - expected).abs() < 1e-6, "{time}");
+    }
+
+    #[test]
+    fn test_parse_divan() {
+        let records = parse_output(DIVAN, &setup("abc"));
+        let benches = records
+            .iter()
+            .map(|r| (r.day, r.bench.as_str(), r.part))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (3, "part1", Some(1)),
+                (3, "part2/many_till", Some(2)),
+                (3, "part2/while", Some(2)),
+                (4, "part1", Some(1)),
+            ],
+            benches
+        );
+        let part1 = &records[0];
+        assert_eq!(Tool::Divan, part1.tool);
+        assert_eq!(Some(27_700.0), part1.fastest.map(f64::round));
+        assert_eq!(Some(27_910.0), part1.median.map(f64::round));
+        assert_eq!(Some(29_060.0), part1.mean.map(f64::round));
+        assert_eq!(Some(100), part1.samples);
+        assert_eq!("abc", part1.setup.commit);
+    }
+
+    #[test]
+    fn test_parse_criterion() {
+        let records = parse_output(CRITERION, &setup("abc"));
+        assert_eq!(2, records.len());
+        assert_eq!("part1", records[0].bench);
+        assert_eq!(Some(270_280.0), records[0].mean.map(f64::round));
+        assert_eq!(Some(100), records[0].samples);
+        assert_eq!("part2/a_rather_long_benchmark_name", records[1].bench);
+        assert_eq!(Some(2), records[1].part);
+        assert_eq!(Some(1_561_600.0), records[1].typical().map(f64::round));
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut history = History::default();
+        history
+            .records
+            .extend(parse_output(DIVAN, &setup("aaaaaaa")));
+        let mut slower = parse_output(DIVAN, &setup("bbbbbbb"));
+        slower[0].median = slower[0].median.map(|median| median * 1.10);
+        slower[1].median = slower[1].median.map(|median| median * 1.02);
+        slower.pop();
+        history.records.extend(slower);
+
+        let comparisons = history.compare("aaa", "bbbbbbb0123456789", 5.0);
+        let flagged = comparisons
+            .iter()
+            .map(|c| (c.bench.as_str(), c.regression))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("part1", true),
+                ("part2/many_till", false),
+                ("part2/while", false)
+            ],
+            flagged
+        );
+        assert!((comparisons[0].change - 10.0).abs() < 1e-9);
+
+        let trends = history.trends(Some(3));
+        assert_eq!(3, trends.len());
+        assert_eq!(2, trends[&(3, "part1", Tool::Divan)].len());
+        assert!(render_trends(&trends).contains("+10.00%"));
+
+        // criterion times of the same benchmark make a trend of their own
+        history.records.extend(parse_output(
+            &DIVAN.replace("day_03", "day_01"),
+            &setup("ccccccc"),
+        ));
+        history
+            .records
+            .extend(parse_output(CRITERION, &setup("ccccccc")));
+        let trends = history.trends(Some(1));
+        assert_eq!(1, trends[&(1, "part1", Tool::Divan)].len());
+        assert_eq!(1, trends[&(1, "part1", Tool::Criterion)].len());
+    }
+
+    #[test]
+    fn test_save_and_load() -> miette::Result<()> {
+        let dir = tempfile::tempdir().into_diagnostic()?;
+        let path = dir.path().join(BENCH_HISTORY_FILE);
+        assert_eq!(History::default(), History::load(&path)?);
+
+        let history = History {
+            records: parse_output(&format!("{DIVAN}{CRITERION}"), &setup("abc")),
+        };
+        history.save(&path)?;
+        assert_eq!(history, History::load(&path)?);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(38_370.0, "38.37 µs")]
+    #[case(2_106_000.0, "2.11 ms")]
+    #[case(950.0, "950.00 ns")]
+    fn test_nanos(#[case] nanos: f64, #[case] expected: &str) {
+        assert_eq!(expected, Nanos(nanos).to_string());
+    }
+}
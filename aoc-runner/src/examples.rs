@@ -0,0 +1,334 @@
+use std::path::Path;
+
+use nom::{
+    branch::alt,
+    bytes::complete::{is_not, tag, take_until},
+    character::complete::alphanumeric1,
+    combinator::map,
+    sequence::{delimited, preceded, terminated, tuple},
+    IResult,
+};
+
+/// Directory of a day crate holding the example inputs, apart from the `examples` cargo builds.
+pub const EXAMPLES_DIR: &str = "examples-input";
+
+/// The rstest case of the day template, replaced by the extracted ones.
+pub const CASE_PLACEHOLDER: &str = "    #[case(\"\", \"\")]\n";
+
+/// An example of the puzzle description along with its emphasized answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answer: String,
+}
+
+/// An rstest case reading its input from a file of the examples directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    pub file: String,
+    pub answer: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token<'a> {
+    Open { name: &'a str, attributes: &'a str },
+    Close(&'a str),
+    Text(&'a str),
+    Skip,
+}
+
+fn parse_token(input: &str) -> IResult<&str, Token<'_>> {
+    alt((
+        map(is_not("<"), Token::Text),
+        map(
+            delimited(tag("<!--"), take_until("-->"), tag("-->")),
+            |_| Token::Skip,
+        ),
+        map(delimited(tag("<!"), take_until(">"), tag(">")), |_| {
+            Token::Skip
+        }),
+        map(
+            delimited(
+                tag("</"),
+                alphanumeric1,
+                terminated(take_until(">"), tag(">")),
+            ),
+            Token::Close,
+        ),
+        map(
+            tuple((
+                preceded(tag("<"), alphanumeric1),
+                terminated(take_until(">"), tag(">")),
+            )),
+            |(name, attributes)| Token::Open { name, attributes },
+        ),
+        // a lone `<` is just text
+        map(tag("<"), Token::Text),
+    ))(input)
+}
+
+fn tokens(mut html: &str) -> impl Iterator<Item = Token<'_>> {
+    std::iter::from_fn(move || {
+        let (rest, token) = parse_token(html).ok()?;
+        html = rest;
+        Some(token)
+    })
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[derive(Default)]
+struct Article {
+    blocks: Vec<(String, Option<String>)>,
+    // an answer emphasized before any example, about the previous part's example
+    carried: Option<String>,
+}
+
+/// Extract the examples of each part from a puzzle page.
+///
+/// Every `<pre><code>` block of a part's description is paired with the last emphasized
+/// `<code><em>` value that follows it, blocks without one are not examples. A part without
+/// any block reuses the last example of the previous part.
+pub fn extract(html: &str) -> Vec<Vec<Example>> {
+    let mut parts: Vec<Vec<Example>> = Vec::new();
+    let mut article: Option<Article> = None;
+    let (mut pre, mut code, mut em) = (false, false, false);
+    let mut text = String::new();
+
+    for token in tokens(html) {
+        match token {
+            Token::Open {
+                name: "article",
+                attributes,
+            } if attributes.contains("day-desc") => article = Some(Article::default()),
+            Token::Close("article") => {
+                if let Some(article) = article.take() {
+                    let previous = parts.last().and_then(|examples| examples.last());
+                    let examples = match (&article.carried, previous) {
+                        (Some(answer), Some(previous)) if article.blocks.is_empty() => {
+                            vec![Example {
+                                input: previous.input.clone(),
+                                answer: answer.clone(),
+                            }]
+                        }
+                        _ => article
+                            .blocks
+                            .into_iter()
+                            .filter_map(|(input, answer)| {
+                                Some(Example {
+                                    input,
+                                    answer: answer?,
+                                })
+                            })
+                            .collect(),
+                    };
+                    parts.push(examples);
+                }
+            }
+            Token::Open { name: "pre", .. } => {
+                pre = true;
+                text.clear();
+            }
+            Token::Close("pre") => {
+                pre = false;
+                if let Some(article) = &mut article {
+                    article.blocks.push((decode_entities(&text), None));
+                }
+                text.clear();
+            }
+            Token::Open { name: "code", .. } => code = true,
+            Token::Open { name: "em", .. } => em = true,
+            Token::Close(name @ ("code" | "em")) => {
+                if name == "code" {
+                    code = false;
+                } else {
+                    em = false;
+                }
+                let answer = decode_entities(text.trim());
+                if pre || answer.is_empty() {
+                    continue;
+                }
+                text.clear();
+                if let Some(article) = &mut article {
+                    match article.blocks.last_mut() {
+                        Some((_, last)) => *last = Some(answer),
+                        None => article.carried = Some(answer),
+                    }
+                }
+            }
+            Token::Text(t) if pre || (code && em) => text.push_str(t),
+            _ => {}
+        }
+    }
+    parts
+}
+
+/// Write the examples into `crate_dir/examples`, each distinct input once, and
+/// return the rstest cases of each part.
+pub fn write_examples(crate_dir: &Path, parts: &[Vec<Example>]) -> std::io::Result<Vec<Vec<Case>>> {
+    let dir = crate_dir.join(EXAMPLES_DIR);
+    let mut inputs: Vec<&str> = Vec::new();
+    let mut cases = Vec::with_capacity(parts.len());
+    for examples in parts {
+        let mut part_cases = Vec::with_capacity(examples.len());
+        for example in examples {
+            let index = match inputs.iter().position(|input| *input == example.input) {
+                Some(index) => index,
+                None => {
+                    inputs.push(&example.input);
+                    inputs.len() - 1
+                }
+            };
+            part_cases.push(Case {
+                file: format!("example{}.txt", index + 1),
+                answer: example.answer.clone(),
+            });
+        }
+        cases.push(part_cases);
+    }
+
+    if !inputs.is_empty() {
+        std::fs::create_dir_all(&dir)?;
+    }
+    for (index, input) in inputs.iter().enumerate() {
+        std::fs::write(dir.join(format!("example{}.txt", index + 1)), input)?;
+    }
+    Ok(cases)
+}
+
+/// The rstest case attributes of `cases`, as found in `src/partN.rs`.
+pub fn render_cases(cases: &[Case]) -> String {
+    cases
+        .iter()
+        .map(|case| {
+            format!(
+                "    #[case(include_str!(\"../{EXAMPLES_DIR}/{}\"), {:?})]\n",
+                case.file, case.answer
+            )
+        })
+        .collect()
+}
+
+/// Replace the template's placeholder case of `source` with `cases`, `None` once it is gone.
+pub fn insert_cases(source: &str, cases: &[Case]) -> Option<String> {
+    source
+        .contains(CASE_PLACEHOLDER)
+        .then(|| source.replacen(CASE_PLACEHOLDER, &render_cases(cases), 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<head><!-- <article class="day-desc"> --><title>Day 1 - Advent of Code 2023</title></head>
+<body>
+<main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+</code></pre>
+<p>In this example, the calibration values of these four lines are <code>12</code>, <code>38</code>, <code>15</code>, and <code>77</code>. Adding these together produces <code><em>142</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>56042</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>two1nine
+<em>eight</em>wothree &lt;3 &amp;
+</code></pre>
+<p>Adding these together produces <em><code>281</code></em>.</p>
+<pre><code>just an illustration
+</code></pre>
+</article>
+</main>
+</body>
+</html>"#;
+
+    #[test]
+    fn test_extract() {
+        let parts = extract(PAGE);
+        assert_eq!(
+            vec![
+                vec![Example {
+                    input: "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n".to_string(),
+                    answer: "142".to_string()
+                }],
+                vec![Example {
+                    input: "two1nine\neightwothree <3 &\n".to_string(),
+                    answer: "281".to_string()
+                }],
+            ],
+            parts
+        );
+    }
+
+    #[test]
+    fn test_extract_reuses_previous_example() {
+        let page = r#"<article class="day-desc"><pre><code>1 2
+</code></pre><p>total <code><em>3</em></code></p></article>
+<article class="day-desc"><p>now it is <code><em>2</em></code></p></article>"#;
+        let parts = extract(page);
+        assert_eq!(2, parts.len());
+        assert_eq!("1 2\n", parts[1][0].input);
+        assert_eq!("2", parts[1][0].answer);
+    }
+
+    #[rstest]
+    #[case("<b>", Token::Open { name: "b", attributes: "" })]
+    #[case("<article class=\"day-desc\">", Token::Open { name: "article", attributes: " class=\"day-desc\"" })]
+    #[case("</code>", Token::Close("code"))]
+    #[case("<!-- a > b -->", Token::Skip)]
+    #[case("a < b", Token::Text("a "))]
+    #[case("< b", Token::Text("<"))]
+    fn test_parse_token(#[case] input: &str, #[case] expected: Token) {
+        assert_eq!(expected, parse_token(input).unwrap().1);
+    }
+
+    #[test]
+    fn test_write_examples() -> miette::Result<()> {
+        use miette::IntoDiagnostic;
+
+        let dir = tempfile::tempdir().into_diagnostic()?;
+        let example = |input: &str, answer: &str| Example {
+            input: input.to_string(),
+            answer: answer.to_string(),
+        };
+        let parts = [
+            vec![example("a\n", "1"), example("b\n", "2")],
+            vec![example("a\n", "3")],
+        ];
+        let cases = write_examples(dir.path(), &parts).into_diagnostic()?;
+        assert_eq!("example1.txt", cases[1][0].file);
+        assert_eq!("example2.txt", cases[0][1].file);
+        let read = |file| std::fs::read_to_string(dir.path().join(EXAMPLES_DIR).join(file));
+        assert_eq!("b\n", read("example2.txt").into_diagnostic()?);
+        assert!(read("example3.txt").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_cases() {
+        let source = "    #[rstest]\n    #[case(\"\", \"\")]\n    fn test_process() {}\n";
+        let cases = [Case {
+            file: "example1.txt".to_string(),
+            answer: "142".to_string(),
+        }];
+        let updated = insert_cases(source, &cases).expect("placeholder is there");
+        assert_eq!(
+            "    #[rstest]\n    #[case(include_str!(\"../examples-input/example1.txt\"), \"142\")]\n    fn test_process() {}\n",
+            updated
+        );
+        assert_eq!(None, insert_cases(&updated, &cases));
+    }
+}
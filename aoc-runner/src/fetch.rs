@@ -0,0 +1,377 @@
+use std::{path::Path, time::Duration};
+
+use aoc_common::input;
+use miette::Diagnostic;
+use thiserror::Error;
+
+/// Where puzzle inputs are downloaded from.
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable holding the `session` cookie of a logged in browser.
+pub const SESSION_VAR: &str = "SESSION";
+
+// the inputs of a day, both parts share the same puzzle input
+const PARTS: [u8; 2] = [1, 2];
+
+// how much of an unexpected response is quoted in an error
+const EXCERPT_LEN: usize = 80;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum FetchError {
+    #[error("no session cookie, which inputs and submissions need")]
+    #[diagnostic(
+        code(aoc::fetch::no_session),
+        help("set `SESSION` to the `session` cookie of adventofcode.com, e.g. in your .env file")
+    )]
+    NoSession,
+
+    #[error("request to `{url}` failed")]
+    #[diagnostic(code(aoc::fetch::request))]
+    Request {
+        url: String,
+        #[source]
+        source: Box<ureq::Error>,
+    },
+
+    #[error("the session is missing or expired")]
+    #[diagnostic(
+        code(aoc::fetch::logged_out),
+        help("refresh `SESSION` with the `session` cookie of a logged in browser")
+    )]
+    LoggedOut,
+
+    #[error("{year} day {day} is not unlocked yet")]
+    #[diagnostic(
+        code(aoc::fetch::locked),
+        help("wait for the puzzle to unlock rather than requesting it repeatedly")
+    )]
+    Locked { year: u16, day: u8 },
+
+    #[error("`{url}` answered with status {status}: {excerpt}")]
+    #[diagnostic(code(aoc::fetch::status))]
+    Status {
+        url: String,
+        status: u16,
+        excerpt: String,
+    },
+
+    #[error("expected a puzzle input, got a web page: {excerpt}")]
+    #[diagnostic(code(aoc::fetch::not_an_input))]
+    NotAnInput { excerpt: String },
+
+    #[error("unexpected answer to a submission: {excerpt}")]
+    #[diagnostic(code(aoc::fetch::unknown_verdict))]
+    UnknownVerdict { excerpt: String },
+
+    #[error("the puzzle input is empty")]
+    #[diagnostic(code(aoc::fetch::empty))]
+    Empty,
+
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+}
+
+/// How the inputs of a day were obtained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    /// Valid inputs were already there, the server was not contacted.
+    Cached,
+    Downloaded,
+}
+
+/// Downloads puzzle inputs, keeping them as `day-XX/inputN.txt` so they are only requested once.
+pub struct Fetcher {
+    agent: ureq::Agent,
+    base_url: String,
+    session: Option<String>,
+}
+
+impl Fetcher {
+    pub fn new(base_url: impl Into<String>, session: Option<String>) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .user_agent("github.com/ceffo/aoc input fetcher")
+            .build()
+            .into();
+        Self {
+            agent,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.filter(|session| !session.trim().is_empty()),
+        }
+    }
+
+    /// Make sure `inputs_dir` holds a valid input for `day` of `year`, downloading it when needed.
+    ///
+    /// With `force` the input is downloaded again, but a bad download never replaces it.
+    pub fn fetch(
+        &self,
+        year: u16,
+        day: u8,
+        inputs_dir: &Path,
+        force: bool,
+    ) -> Result<Fetched, FetchError> {
+        let paths = PARTS.map(|part| input::input_path(inputs_dir, day, part));
+        let cached = paths.iter().all(|path| {
+            std::fs::read_to_string(path).is_ok_and(|content| check_input(&content).is_ok())
+        });
+        if cached && !force {
+            return Ok(Fetched::Cached);
+        }
+
+        let content = self.download(year, day)?;
+        for path in &paths {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(path, &content)?;
+        }
+        Ok(Fetched::Downloaded)
+    }
+
+    /// Download the input of `day` of `year`, failing on anything but a puzzle input.
+    pub fn download(&self, year: u16, day: u8) -> Result<String, FetchError> {
+        if self.session.is_none() {
+            return Err(FetchError::NoSession);
+        }
+        let body = self.get(
+            &format!("{}/{year}/day/{day}/input", self.base_url),
+            year,
+            day,
+        )?;
+        check_input(&body)?;
+        Ok(body)
+    }
+
+    /// Download the puzzle page of `day` of `year`, part two only shows up with a session.
+    pub fn puzzle_page(&self, year: u16, day: u8) -> Result<String, FetchError> {
+        self.get(&format!("{}/{year}/day/{day}", self.base_url), year, day)
+    }
+
+    // GET `url`, with the session cookie when there is one
+    fn get(&self, url: &str, year: u16, day: u8) -> Result<String, FetchError> {
+        let mut request = self.agent.get(url);
+        if let Some(session) = &self.session {
+            request = request.header("Cookie", format!("session={session}"));
+        }
+        read_response(request.call(), url, year, day)
+    }
+
+    /// POST `form` to the page at `path` of the site, which needs a session.
+    pub(crate) fn post_form(
+        &self,
+        path: &str,
+        form: &[(&str, &str)],
+        year: u16,
+        day: u8,
+    ) -> Result<String, FetchError> {
+        let session = self.session.as_deref().ok_or(FetchError::NoSession)?;
+        let url = format!("{}{path}", self.base_url);
+        let response = self
+            .agent
+            .post(&url)
+            .header("Cookie", format!("session={session}"))
+            .send_form(form.iter().copied());
+        read_response(response, &url, year, day)
+    }
+}
+
+fn read_response(
+    response: Result<ureq::http::Response<ureq::Body>, ureq::Error>,
+    url: &str,
+    year: u16,
+    day: u8,
+) -> Result<String, FetchError> {
+    let request_error = |source| FetchError::Request {
+        url: url.to_string(),
+        source: Box::new(source),
+    };
+    let mut response = response.map_err(request_error)?;
+    let status = response.status().as_u16();
+    let body = response
+        .body_mut()
+        .read_to_string()
+        .map_err(request_error)?;
+
+    match status {
+        200 => Ok(body),
+        _ if is_logged_out(&body) => Err(FetchError::LoggedOut),
+        _ if is_locked(&body) => Err(FetchError::Locked { year, day }),
+        _ => Err(FetchError::Status {
+            url: url.to_string(),
+            status,
+            excerpt: excerpt(&body),
+        }),
+    }
+}
+
+// the pages served instead of an input, whatever their status
+fn is_logged_out(body: &str) -> bool {
+    body.contains("Please log in") || body.contains("Puzzle inputs differ by user")
+}
+
+fn is_locked(body: &str) -> bool {
+    body.contains("Please don't repeatedly request this endpoint")
+}
+
+/// Reject anything that does not look like a puzzle input, e.g. an error page saved by mistake.
+pub fn check_input(content: &str) -> Result<(), FetchError> {
+    let start = content.trim_start();
+    let html = ["<!doctype", "<html"].iter().any(|tag| {
+        start
+            .get(..tag.len())
+            .is_some_and(|s| s.eq_ignore_ascii_case(tag))
+    });
+    if start.is_empty() {
+        Err(FetchError::Empty)
+    } else if html || is_logged_out(content) || is_locked(content) {
+        Err(FetchError::NotAnInput {
+            excerpt: excerpt(content),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) fn excerpt(body: &str) -> String {
+    let line = body.trim().lines().next().unwrap_or_default();
+    line.chars().take(EXCERPT_LEN).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stand_in::serve;
+    use rstest::rstest;
+
+    const INPUT: &str = "1abc2\npqr3stu8vwx\n";
+
+    fn fetcher(url: &str) -> Fetcher {
+        Fetcher::new(url, Some("cookie".to_string()))
+    }
+
+    #[test]
+    fn test_fetch_then_cached() -> miette::Result<()> {
+        use miette::IntoDiagnostic;
+
+        let (url, requests) = serve(vec![(200, INPUT)]);
+        let dir = tempfile::tempdir().into_diagnostic()?;
+
+        assert_eq!(
+            Fetched::Downloaded,
+            fetcher(&url).fetch(2024, 5, dir.path(), false)?
+        );
+        let request = requests.recv().into_diagnostic()?;
+        assert_eq!("GET /2024/day/5/input HTTP/1.1", request.head[0]);
+        assert_eq!(Some("session=cookie"), request.header("cookie"));
+        for part in PARTS {
+            let path = input::input_path(dir.path(), 5, part);
+            assert_eq!(INPUT, std::fs::read_to_string(path).into_diagnostic()?);
+        }
+
+        // the server is gone, so only the cache can answer
+        let offline = Fetcher::new(url, None);
+        assert_eq!(Fetched::Cached, offline.fetch(2024, 5, dir.path(), false)?);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(
+        400,
+        "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+    )]
+    #[case(
+        404,
+        "Please don't repeatedly request this endpoint before it unlocks! \
+         The calendar countdown is synchronized with the server time; \
+         the link will be enabled on the calendar the instant this puzzle becomes available.\n"
+    )]
+    #[case(500, "Internal Server Error\n")]
+    #[case(200, "<!DOCTYPE html>\n<html lang=\"en-us\">\n")]
+    #[case(200, "")]
+    fn test_bad_download_keeps_input(
+        #[case] status: u16,
+        #[case] body: &'static str,
+    ) -> miette::Result<()> {
+        use miette::IntoDiagnostic;
+
+        let (url, _requests) = serve(vec![(status, body)]);
+        let dir = tempfile::tempdir().into_diagnostic()?;
+        let path = input::input_path(dir.path(), 1, 1);
+        std::fs::create_dir_all(path.parent().unwrap()).into_diagnostic()?;
+        std::fs::write(&path, INPUT).into_diagnostic()?;
+
+        let err = fetcher(&url).fetch(2023, 1, dir.path(), true).unwrap_err();
+        let expected = match (status, body) {
+            (400, _) => matches!(err, FetchError::LoggedOut),
+            (404, _) => matches!(err, FetchError::Locked { year: 2023, day: 1 }),
+            (500, _) => matches!(err, FetchError::Status { status: 500, .. }),
+            (_, "") => matches!(err, FetchError::Empty),
+            _ => matches!(err, FetchError::NotAnInput { .. }),
+        };
+        assert!(expected, "unexpected error {err:?}");
+        assert_eq!(INPUT, std::fs::read_to_string(&path).into_diagnostic()?);
+        assert!(!input::input_path(dir.path(), 1, 2).exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_bad_cached_input_is_replaced() -> miette::Result<()> {
+        use miette::IntoDiagnostic;
+
+        let (url, _requests) = serve(vec![(200, INPUT)]);
+        let dir = tempfile::tempdir().into_diagnostic()?;
+        for part in PARTS {
+            let path = input::input_path(dir.path(), 3, part);
+            std::fs::create_dir_all(path.parent().unwrap()).into_diagnostic()?;
+            std::fs::write(
+                &path,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            )
+            .into_diagnostic()?;
+        }
+
+        assert_eq!(
+            Fetched::Downloaded,
+            fetcher(&url).fetch(2023, 3, dir.path(), false)?
+        );
+        let path = input::input_path(dir.path(), 3, 2);
+        assert_eq!(INPUT, std::fs::read_to_string(path).into_diagnostic()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_puzzle_page_without_session() -> miette::Result<()> {
+        use miette::IntoDiagnostic;
+
+        let (url, requests) = serve(vec![(200, "<html></html>")]);
+        let page = Fetcher::new(url, None).puzzle_page(2023, 8)?;
+        assert_eq!("<html></html>", page);
+        let request = requests.recv().into_diagnostic()?;
+        assert_eq!("GET /2023/day/8 HTTP/1.1", request.head[0]);
+        assert_eq!(None, request.header("cookie"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_download_needs_a_session() {
+        let fetcher = Fetcher::new(BASE_URL, None);
+        assert!(matches!(
+            fetcher.download(2023, 1),
+            Err(FetchError::NoSession)
+        ));
+    }
+
+    #[rstest]
+    #[case(INPUT, true)]
+    #[case("  \n", false)]
+    #[case("<html><body>Oops</body></html>", false)]
+    #[case(
+        "Please don't repeatedly request this endpoint before it unlocks!",
+        false
+    )]
+    fn test_check_input(#[case] content: &str, #[case] valid: bool) {
+        assert_eq!(valid, check_input(content).is_ok());
+    }
+}
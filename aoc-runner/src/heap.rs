@@ -0,0 +1,230 @@
+use std::{fmt::Display, path::Path};
+
+use miette::{Context, IntoDiagnostic};
+use serde::{Deserialize, Serialize};
+
+use crate::bench::Setup;
+
+/// Name of the heap profile, stored in the year directory next to the bench history.
+pub const HEAP_PROFILE_FILE: &str = "heap-profile.toml";
+
+/// What a solution allocated on the heap, as counted by dhat.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Usage {
+    /// Bytes allocated over the whole run.
+    pub total_bytes: u64,
+    /// Allocations made over the whole run.
+    pub total_blocks: u64,
+    /// Bytes live when the heap was at its largest.
+    pub peak_bytes: u64,
+    /// Allocations live when the heap was at its largest.
+    pub peak_blocks: u64,
+}
+
+/// The heap usage of one part of a day on its puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    #[serde(flatten)]
+    pub usage: Usage,
+    #[serde(flatten)]
+    pub setup: Setup,
+}
+
+/// The last heap usage measured for each part of a year, by day and part:
+///
+/// ```toml
+/// [[heap]]
+/// day = 1
+/// part = 1
+/// total_bytes = 23456
+/// total_blocks = 1002
+/// peak_bytes = 21845
+/// peak_blocks = 3
+/// commit = "56e8c58"
+/// rustc = "rustc 1.85.0-nightly"
+/// cpu = "Apple M1"
+/// at = 1701406800
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    #[serde(default, rename = "heap")]
+    pub records: Vec<Record>,
+}
+
+impl Profile {
+    /// Load the profile at `path`, a missing file holds no records.
+    pub fn load(path: &Path) -> miette::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("invalid heap profile `{}`", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).into_diagnostic(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> miette::Result<()> {
+        let content = toml::to_string(self).into_diagnostic()?;
+        std::fs::write(path, content)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("write heap profile `{}`", path.display()))
+    }
+
+    /// Replace the records of the parts measured again by their new `records`.
+    pub fn update(&mut self, records: impl IntoIterator<Item = Record>) {
+        for record in records {
+            self.records
+                .retain(|r| (r.day, r.part) != (record.day, record.part));
+            self.records.push(record);
+        }
+        self.records.sort_by_key(|record| (record.day, record.part));
+    }
+}
+
+/// Run `f` under a dhat heap profiler and report what it allocated.
+///
+/// The profile is also written to `json` for `dh_view.html` when given.
+/// The binary must use [`dhat::Alloc`] as its global allocator for anything to be counted.
+#[cfg(feature = "dhat-heap")]
+pub fn measure<T>(json: Option<&Path>, f: impl FnOnce() -> T) -> miette::Result<(T, Usage)> {
+    let builder = dhat::Profiler::builder();
+    let profiler = match json {
+        Some(path) => builder.file_name(path),
+        None => builder.testing(),
+    }
+    .build();
+    let value = f();
+    let stats = dhat::HeapStats::get();
+    drop(profiler);
+
+    if stats.total_blocks == 0 && !aoc_common::heap::counts_allocations() {
+        miette::bail!(
+            help =
+                "add `#[global_allocator] static ALLOC: dhat::Alloc = dhat::Alloc;` to the runner",
+            "dhat counted no allocation at all"
+        );
+    }
+    let usage = Usage {
+        total_bytes: stats.total_bytes,
+        total_blocks: stats.total_blocks,
+        peak_bytes: stats.max_bytes as u64,
+        peak_blocks: stats.max_blocks as u64,
+    };
+    Ok((value, usage))
+}
+
+#[cfg(not(feature = "dhat-heap"))]
+pub fn measure<T>(_json: Option<&Path>, _f: impl FnOnce() -> T) -> miette::Result<(T, Usage)> {
+    miette::bail!(
+        help = "run it with `--profile dhat --features dhat-heap`, or `just heap`",
+        "the runner was built without heap profiling"
+    )
+}
+
+/// A size in bytes, e.g. `1.50 KiB`.
+pub struct Bytes(pub u64);
+
+impl Display for Bytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bytes = self.0 as f64;
+        let formatted = match self.0 {
+            b if b >= 1 << 30 => format!("{:.2} GiB", bytes / f64::from(1 << 30)),
+            b if b >= 1 << 20 => format!("{:.2} MiB", bytes / f64::from(1 << 20)),
+            b if b >= 1 << 10 => format!("{:.2} KiB", bytes / f64::from(1 << 10)),
+            b => format!("{b} B"),
+        };
+        f.pad(&formatted)
+    }
+}
+
+pub fn render(records: &[Record]) -> String {
+    let mut output = format!(
+        "{:<6} {:<5}  {:>12} {:>12}  {:>12} {:>12}\n",
+        "day", "part", "total", "allocations", "peak", "peak allocs"
+    );
+    for record in records {
+        let usage = &record.usage;
+        output.push_str(&format!(
+            "day-{:02} part{}  {:>12} {:>12}  {:>12} {:>12}\n",
+            record.day,
+            record.part,
+            Bytes(usage.total_bytes),
+            usage.total_blocks,
+            Bytes(usage.peak_bytes),
+            usage.peak_blocks
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn record(day: u8, part: u8, total_bytes: u64, commit: &str) -> Record {
+        Record {
+            day,
+            part,
+            usage: Usage {
+                total_bytes,
+                total_blocks: 10,
+                peak_bytes: total_bytes / 2,
+                peak_blocks: 2,
+            },
+            setup: Setup {
+                commit: commit.to_string(),
+                ..Setup::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_update() {
+        let mut profile = Profile::default();
+        profile.update([record(5, 2, 100, "abc"), record(1, 1, 200, "abc")]);
+        profile.update([record(5, 2, 300, "def")]);
+        assert_eq!(
+            vec![record(1, 1, 200, "abc"), record(5, 2, 300, "def")],
+            profile.records
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let output = render(&[record(5, 2, 3 << 20, "abc")]);
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(2, lines.len(), "{output}");
+        assert!(lines[0].starts_with("day    part"), "{output}");
+        assert!(lines[1].starts_with("day-05 part2"), "{output}");
+        assert!(lines[1].contains("3.00 MiB"), "{output}");
+        assert!(lines[1].contains("1.50 MiB"), "{output}");
+    }
+
+    #[test]
+    fn test_save_and_load() -> miette::Result<()> {
+        let dir = tempfile::tempdir().into_diagnostic()?;
+        let path = dir.path().join(HEAP_PROFILE_FILE);
+        assert_eq!(Profile::default(), Profile::load(&path)?);
+
+        let profile = Profile {
+            records: vec![record(1, 1, 200, "abc"), record(1, 2, 400, "abc")],
+        };
+        profile.save(&path)?;
+        let content = std::fs::read_to_string(&path).into_diagnostic()?;
+        assert!(content.contains("[[heap]]"), "{content}");
+        assert!(content.contains("peak_bytes = 200"), "{content}");
+        assert_eq!(profile, Profile::load(&path)?);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(512, "512 B")]
+    #[case(1536, "1.50 KiB")]
+    #[case(3 << 20, "3.00 MiB")]
+    fn test_bytes(#[case] bytes: u64, #[case] expected: &str) {
+        assert_eq!(expected, Bytes(bytes).to_string());
+    }
+}
@@ -0,0 +1,234 @@
+use std::{
+    collections::BTreeMap,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use miette::{Context, Diagnostic, IntoDiagnostic};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::submit::Verdict;
+
+/// Name of the submissions file, stored next to the inputs of a day.
+pub const SUBMISSIONS_FILE: &str = "submissions.toml";
+
+/// An answer submitted to the site along with its verdict.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch.
+    pub at: u64,
+}
+
+/// Why an answer should not be submitted, given the previous attempts.
+#[derive(Error, Diagnostic, Debug, Clone, PartialEq, Eq)]
+pub enum Objection {
+    #[error("`{answer}` was already submitted: {verdict}")]
+    #[diagnostic(
+        code(aoc::submit::rejected),
+        help("pass `--force` to submit it anyway")
+    )]
+    Rejected { answer: String, verdict: Verdict },
+
+    #[error("`{answer}` is not above `{bound}`, which was too low")]
+    #[diagnostic(code(aoc::submit::too_low), help("pass `--force` to submit it anyway"))]
+    TooLow { answer: String, bound: String },
+
+    #[error("`{answer}` is not below `{bound}`, which was too high")]
+    #[diagnostic(
+        code(aoc::submit::too_high),
+        help("pass `--force` to submit it anyway")
+    )]
+    TooHigh { answer: String, bound: String },
+
+    #[error("`{accepted}` was already accepted, not `{answer}`")]
+    #[diagnostic(
+        code(aoc::submit::accepted),
+        help("pass `--force` to submit it anyway")
+    )]
+    Accepted { answer: String, accepted: String },
+}
+
+/// Every submission of a day, one array per part:
+///
+/// ```toml
+/// [[part1]]
+/// answer = "42"
+/// verdict = "too_low"
+/// at = 1701406800
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct History(BTreeMap<String, Vec<Attempt>>);
+
+impl History {
+    /// Load the history at `path`, a missing file holds no attempts.
+    pub fn load(path: &Path) -> miette::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content)
+                .wrap_err_with(|| format!("invalid submissions file `{}`", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).into_diagnostic(),
+        }
+    }
+
+    pub fn parse(content: &str) -> miette::Result<Self> {
+        toml::from_str(content).into_diagnostic()
+    }
+
+    pub fn save(&self, path: &Path) -> miette::Result<()> {
+        let content = toml::to_string(self).into_diagnostic()?;
+        std::fs::write(path, content)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("write submissions file `{}`", path.display()))
+    }
+
+    pub fn attempts(&self, part: u8) -> &[Attempt] {
+        self.0
+            .get(&format!("part{part}"))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Record an attempt made just now.
+    pub fn record(&mut self, part: u8, answer: impl Into<String>, verdict: Verdict) {
+        let at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        self.0
+            .entry(format!("part{part}"))
+            .or_default()
+            .push(Attempt {
+                answer: answer.into(),
+                verdict,
+                at,
+            });
+    }
+
+    // the numeric answers that got `verdict`
+    fn numbers(&self, part: u8, verdict: Verdict) -> impl Iterator<Item = (i128, &str)> {
+        self.attempts(part)
+            .iter()
+            .filter(move |attempt| attempt.verdict == verdict)
+            .filter_map(|attempt| {
+                Some((attempt.answer.trim().parse().ok()?, attempt.answer.as_str()))
+            })
+    }
+
+    /// Check `answer` against the previous attempts of `part`, before burning a submission on it.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), Objection> {
+        let attempts = self.attempts(part);
+        if let Some(accepted) = attempts.iter().find(|a| a.verdict == Verdict::Correct) {
+            if accepted.answer != answer {
+                return Err(Objection::Accepted {
+                    answer: answer.to_string(),
+                    accepted: accepted.answer.clone(),
+                });
+            }
+        }
+        if let Some(rejected) = attempts
+            .iter()
+            .find(|a| a.answer == answer && a.verdict.is_rejection())
+        {
+            return Err(Objection::Rejected {
+                answer: answer.to_string(),
+                verdict: rejected.verdict,
+            });
+        }
+
+        let Ok(value) = answer.trim().parse::<i128>() else {
+            return Ok(());
+        };
+        if let Some((low, bound)) = self.numbers(part, Verdict::TooLow).max() {
+            if value <= low {
+                return Err(Objection::TooLow {
+                    answer: answer.to_string(),
+                    bound: bound.to_string(),
+                });
+            }
+        }
+        if let Some((high, bound)) = self.numbers(part, Verdict::TooHigh).min() {
+            if value >= high {
+                return Err(Objection::TooHigh {
+                    answer: answer.to_string(),
+                    bound: bound.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::time::Duration;
+
+    fn history(attempts: &[(&str, Verdict)]) -> History {
+        let mut history = History::default();
+        for (answer, verdict) in attempts {
+            history.record(1, *answer, *verdict);
+        }
+        history
+    }
+
+    #[rstest]
+    #[case(&[], "42", Ok(()))]
+    #[case(&[("10", Verdict::TooLow), ("90", Verdict::TooHigh)], "42", Ok(()))]
+    #[case(
+        &[("10", Verdict::TooLow), ("40", Verdict::TooLow)],
+        "40",
+        Err(Objection::Rejected { answer: "40".to_string(), verdict: Verdict::TooLow })
+    )]
+    #[case(
+        &[("40", Verdict::TooLow), ("10", Verdict::TooLow)],
+        "25",
+        Err(Objection::TooLow { answer: "25".to_string(), bound: "40".to_string() })
+    )]
+    #[case(
+        &[("90", Verdict::TooHigh), ("120", Verdict::TooHigh)],
+        "100",
+        Err(Objection::TooHigh { answer: "100".to_string(), bound: "90".to_string() })
+    )]
+    #[case(&[("-5", Verdict::TooLow)], "-3", Ok(()))]
+    #[case(
+        &[("abc", Verdict::Wrong)],
+        "abc",
+        Err(Objection::Rejected { answer: "abc".to_string(), verdict: Verdict::Wrong })
+    )]
+    #[case(&[("42", Verdict::Wait(Duration::from_secs(30)))], "42", Ok(()))]
+    #[case(
+        &[("42", Verdict::Correct)],
+        "43",
+        Err(Objection::Accepted { answer: "43".to_string(), accepted: "42".to_string() })
+    )]
+    fn test_check(
+        #[case] attempts: &[(&str, Verdict)],
+        #[case] answer: &str,
+        #[case] expected: Result<(), Objection>,
+    ) {
+        assert_eq!(expected, history(attempts).check(1, answer));
+    }
+
+    #[test]
+    fn test_save_and_load() -> miette::Result<()> {
+        let dir = tempfile::tempdir().into_diagnostic()?;
+        let path = dir.path().join(SUBMISSIONS_FILE);
+        assert_eq!(History::default(), History::load(&path)?);
+
+        let history = history(&[
+            ("10", Verdict::TooLow),
+            ("20", Verdict::Wait(Duration::from_secs(39))),
+            ("20", Verdict::Correct),
+        ]);
+        history.save(&path)?;
+        let content = std::fs::read_to_string(&path).into_diagnostic()?;
+        assert!(content.contains("verdict = \"too_low\""), "{content}");
+        assert_eq!(history, History::load(&path)?);
+        assert_eq!(3, history.attempts(1).len());
+        assert!(history.attempts(2).is_empty());
+        Ok(())
+    }
+}
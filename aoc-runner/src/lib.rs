@@ -0,0 +1,663 @@
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+mod answers;
+mod bench;
+mod examples;
+mod fetch;
+mod heap;
+mod history;
+#[cfg(test)]
+mod stand_in;
+mod submit;
+mod verify;
+
+use aoc_common::{input, Registry, Selector, Solution, SolutionId};
+use clap::Parser;
+use miette::{Context, IntoDiagnostic};
+use nom::{
+    branch::alt, bytes::complete::tag, character::complete, combinator::all_consuming,
+    sequence::preceded, IResult,
+};
+
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// list the registered solutions
+    List(Selection),
+    /// run the selected solutions on their puzzle input
+    Run(RunArgs),
+    /// check the selected solutions against their recorded answers
+    Verify(VerifyArgs),
+    /// download the puzzle input of a day, unless a valid one is already there
+    Fetch(FetchArgs),
+    /// extract the examples of a puzzle page into test cases of its day crate
+    Examples(ExamplesArgs),
+    /// submit an answer and record it once it is accepted
+    Submit(SubmitArgs),
+    /// record benchmark results and compare them across commits
+    #[clap(subcommand)]
+    Bench(BenchCommand),
+    /// measure the heap usage of the selected solutions on their puzzle input
+    Heap(HeapArgs),
+}
+
+#[derive(clap::Args, Debug)]
+struct Selection {
+    /// day formatted as `day-01` (or just `1`), every day when omitted
+    #[clap(value_parser = parse_day)]
+    day: Option<u8>,
+    /// part formatted as `part1` (or just `1`), both parts when omitted
+    #[clap(value_parser = parse_part)]
+    part: Option<u8>,
+    /// only select the solutions of this year
+    #[clap(long)]
+    year: Option<u16>,
+}
+
+#[derive(clap::Args, Debug)]
+struct RunArgs {
+    #[clap(flatten)]
+    selection: Selection,
+    /// read the input from this file instead, `-` reads it from stdin
+    #[clap(long)]
+    input: Option<PathBuf>,
+    #[clap(flatten)]
+    inputs: Inputs,
+    /// write the spans of the solutions into this file as Chrome trace events, for Perfetto
+    #[clap(long)]
+    trace: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug)]
+struct VerifyArgs {
+    #[clap(flatten)]
+    selection: Selection,
+    #[clap(flatten)]
+    inputs: Inputs,
+    /// record the answers of solutions that have none yet
+    #[clap(long)]
+    record: bool,
+}
+
+#[derive(clap::Args, Debug)]
+struct FetchArgs {
+    /// day formatted as `day-01` (or just `1`)
+    #[clap(value_parser = parse_day)]
+    day: u8,
+    /// year of the puzzle, the year of the registered solutions by default
+    #[clap(long)]
+    year: Option<u16>,
+    /// download the input again, a bad download still never replaces a valid input
+    #[clap(long)]
+    force: bool,
+    #[clap(flatten)]
+    inputs: Inputs,
+    /// `session` cookie of a browser logged in to adventofcode.com
+    #[clap(long, env = fetch::SESSION_VAR, hide_env_values = true)]
+    session: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+struct ExamplesArgs {
+    /// day formatted as `day-01` (or just `1`)
+    #[clap(value_parser = parse_day)]
+    day: u8,
+    /// year of the puzzle, the year of the registered solutions by default
+    #[clap(long)]
+    year: Option<u16>,
+    /// read this saved puzzle page instead of downloading it
+    #[clap(long)]
+    page: Option<PathBuf>,
+    /// `session` cookie of a browser logged in to adventofcode.com, needed for part two
+    #[clap(long, env = fetch::SESSION_VAR, hide_env_values = true)]
+    session: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+struct SubmitArgs {
+    /// day formatted as `day-01` (or just `1`)
+    #[clap(value_parser = parse_day)]
+    day: u8,
+    /// part formatted as `part1` (or just `1`)
+    #[clap(value_parser = parse_part)]
+    part: u8,
+    /// the answer to submit, computed by the solution from the part's input when omitted
+    answer: Option<String>,
+    /// year of the puzzle, the year of the registered solutions by default
+    #[clap(long)]
+    year: Option<u16>,
+    /// submit even an answer that previous submissions show to be wrong
+    #[clap(long)]
+    force: bool,
+    #[clap(flatten)]
+    inputs: Inputs,
+    /// `session` cookie of a browser logged in to adventofcode.com
+    #[clap(long, env = fetch::SESSION_VAR, hide_env_values = true)]
+    session: Option<String>,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum BenchCommand {
+    /// append the results of `cargo bench` (divan or criterion) to the bench history
+    Record {
+        /// read the output of `cargo bench` from this file instead of stdin
+        #[clap(long)]
+        file: Option<PathBuf>,
+        /// do not print the output back while reading it
+        #[clap(long, short)]
+        quiet: bool,
+    },
+    /// print how the benchmarks evolved from one recorded commit to the next
+    Trend {
+        /// day formatted as `day-01` (or just `1`), every day when omitted
+        #[clap(value_parser = parse_day)]
+        day: Option<u8>,
+    },
+    /// compare the benchmarks of two recorded commits, failing on regressions
+    Compare {
+        /// git revision (or recorded commit) of the baseline
+        base: String,
+        /// git revision (or recorded commit) to compare with the baseline
+        head: String,
+        /// slowdown of the median in percent above which a benchmark has regressed
+        #[clap(long, default_value_t = 5.0)]
+        threshold: f64,
+    },
+}
+
+#[derive(clap::Args, Debug)]
+struct HeapArgs {
+    #[clap(flatten)]
+    selection: Selection,
+    #[clap(flatten)]
+    inputs: Inputs,
+    /// also write the dhat profile of each solution into this directory, for `dh_view.html`
+    #[clap(long)]
+    json: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug)]
+struct Inputs {
+    /// directory holding the inputs as `day-XX/inputN.txt`, the year directory by default
+    #[clap(long, env = input::INPUTS_DIR_VAR)]
+    inputs_dir: Option<PathBuf>,
+}
+
+impl Inputs {
+    fn dir<'a>(&'a self, root: &'a Path) -> &'a Path {
+        self.inputs_dir.as_deref().unwrap_or(root)
+    }
+}
+
+impl From<&Selection> for Selector {
+    fn from(selection: &Selection) -> Self {
+        Selector {
+            year: selection.year,
+            day: selection.day,
+            part: selection.part,
+        }
+    }
+}
+
+fn parse_number<'a>(prefix: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, u8> {
+    all_consuming(alt((preceded(tag(prefix), complete::u8), complete::u8)))
+}
+
+fn parse_day(input: &str) -> Result<u8, String> {
+    parse_number("day-")(input)
+        .map(|(_, day)| day)
+        .map_err(|_| format!("day `{input}` must be formatted as `day-01`"))
+}
+
+fn parse_part(input: &str) -> Result<u8, String> {
+    parse_number("part")(input)
+        .map(|(_, part)| part)
+        .map_err(|_| format!("part `{input}` must be formatted as `part1`"))
+}
+
+fn solve(solution: &dyn Solution, input: &str, input_name: &str) -> miette::Result<String> {
+    let _span = tracing::info_span!("solution", id = %solution.id()).entered();
+    let answer = solution
+        .solve(input)
+        .map_err(|e| e.with_source_name(input_name))
+        .wrap_err_with(|| format!("process {}", solution.id()))?;
+    Ok(answer)
+}
+
+fn run(root: &Path, solutions: &[&dyn Solution], args: &RunArgs) -> miette::Result<()> {
+    // an explicit input is read once and shared by the parts of a single day
+    let shared_input = match &args.input {
+        Some(_)
+            if solutions
+                .iter()
+                .any(|s| (s.year(), s.day()) != (solutions[0].year(), solutions[0].day())) =>
+        {
+            miette::bail!("`--input` needs a single day to be selected")
+        }
+        Some(path) if path == Path::new("-") => Some((input::read_stdin()?, "stdin".to_string())),
+        Some(path) => Some((input::read(path)?, path.display().to_string())),
+        None => None,
+    };
+    let inputs_dir = args.inputs.dir(root);
+
+    for solution in solutions {
+        let answer = match &shared_input {
+            Some((input, name)) => solve(*solution, input, name)?,
+            None => {
+                let path = input::input_path(inputs_dir, solution.day(), solution.part());
+                let answer = solve(*solution, &input::read(&path)?, &path.display().to_string())?;
+                // answers already known to be wrong are flagged before anyone submits them
+                let history = load_history(&path)?;
+                if let Err(objection) = history.check(solution.part(), &answer) {
+                    eprintln!("warning: {}: {objection}", solution.id());
+                }
+                answer
+            }
+        };
+        if solutions.len() == 1 {
+            println!("{answer}");
+        } else {
+            println!("{}: {answer}", solution.id());
+        }
+    }
+    if let Some(path) = &args.trace {
+        eprintln!(
+            "traced into `{}`, open it with https://ui.perfetto.dev",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+// the year every registered solution belongs to
+fn registry_year(registry: &Registry) -> miette::Result<u16> {
+    let years = registry
+        .iter()
+        .map(|solution| solution.year())
+        .collect::<BTreeSet<_>>();
+    match years.into_iter().collect::<Vec<_>>()[..] {
+        [year] => Ok(year),
+        _ => miette::bail!("cannot tell which year to fetch, pass `--year`"),
+    }
+}
+
+fn extract_examples(root: &Path, year: u16, args: ExamplesArgs) -> miette::Result<()> {
+    let crate_dir = root.join(format!("day-{:02}", args.day));
+    if !crate_dir.is_dir() {
+        miette::bail!(
+            "no crate at `{}`, create it first with `just create day-{:02}`",
+            crate_dir.display(),
+            args.day
+        );
+    }
+    let page = match &args.page {
+        Some(path) => input::read(path)?,
+        None => fetch::Fetcher::new(fetch::BASE_URL, args.session).puzzle_page(year, args.day)?,
+    };
+
+    let parts = examples::extract(&page);
+    let cases = examples::write_examples(&crate_dir, &parts)
+        .into_diagnostic()
+        .wrap_err("write the examples")?;
+    for (part, cases) in (1..).zip(&cases) {
+        if cases.is_empty() {
+            println!("part{part}: no example with an emphasized answer");
+            continue;
+        }
+        let path = crate_dir.join("src").join(format!("part{part}.rs"));
+        let source = std::fs::read_to_string(&path).into_diagnostic()?;
+        match examples::insert_cases(&source, cases) {
+            Some(updated) => {
+                std::fs::write(&path, updated).into_diagnostic()?;
+                println!(
+                    "part{part}: {} case(s) added to `{}`",
+                    cases.len(),
+                    path.display()
+                );
+            }
+            None => print!(
+                "part{part}: `{}` has no placeholder case left, add these by hand:\n{}",
+                path.display(),
+                examples::render_cases(cases)
+            ),
+        }
+    }
+    Ok(())
+}
+
+// the submissions of the day whose input is at `input_path`
+fn load_history(input_path: &Path) -> miette::Result<history::History> {
+    let day_dir = input_path.parent().expect("input lives in a day directory");
+    history::History::load(&day_dir.join(history::SUBMISSIONS_FILE))
+}
+
+fn submit(registry: &Registry, root: &Path, year: u16, args: SubmitArgs) -> miette::Result<()> {
+    let id = SolutionId {
+        year,
+        day: args.day,
+        part: args.part,
+    };
+    let inputs_dir = args.inputs.dir(root);
+    let path = input::input_path(inputs_dir, args.day, args.part);
+    let answer = match args.answer {
+        Some(answer) => answer,
+        None => {
+            let Some(solution) = registry.get(id) else {
+                miette::bail!("no solution registered for {id}, pass the answer to submit");
+            };
+            solve(solution, &input::read(&path)?, &path.display().to_string())?
+        }
+    };
+
+    let day_dir = path.parent().expect("input lives in a day directory");
+    let history_path = day_dir.join(history::SUBMISSIONS_FILE);
+    let mut history = history::History::load(&history_path)?;
+    match history.check(args.part, &answer) {
+        Err(objection) if !args.force => return Err(objection.into()),
+        Err(objection) => eprintln!("warning: {objection}"),
+        Ok(()) => {}
+    }
+
+    let fetcher = fetch::Fetcher::new(fetch::BASE_URL, args.session);
+    let verdict = fetcher.submit(year, args.day, args.part, &answer)?;
+    println!("{id}: {answer}: {verdict}");
+    history.record(args.part, answer.as_str(), verdict);
+    history.save(&history_path)?;
+    if verdict == submit::Verdict::Correct {
+        let answers_path = day_dir.join(answers::ANSWERS_FILE);
+        let mut answers = answers::Answers::load(&answers_path)?;
+        answers.set(&verify::input_key(&path), args.part, answer);
+        answers.save(&answers_path)?;
+        println!("recorded in `{}`", answers_path.display());
+    }
+    Ok(())
+}
+
+fn bench(root: &Path, command: BenchCommand) -> miette::Result<()> {
+    let path = root.join(bench::BENCH_HISTORY_FILE);
+    let mut history = bench::History::load(&path)?;
+    match command {
+        BenchCommand::Record { file, quiet } => {
+            let output = match &file {
+                Some(file) => std::fs::read_to_string(file)
+                    .into_diagnostic()
+                    .wrap_err_with(|| format!("read `{}`", file.display()))?,
+                None => std::io::read_to_string(std::io::stdin()).into_diagnostic()?,
+            };
+            if !quiet && file.is_none() {
+                print!("{output}");
+            }
+            let records = bench::parse_output(&output, &bench::Setup::current());
+            if records.is_empty() {
+                miette::bail!("no divan or criterion results in the output");
+            }
+            eprintln!(
+                "recorded {} benchmark(s) at {} into `{}`",
+                records.len(),
+                records[0].setup.commit,
+                path.display()
+            );
+            history.records.extend(records);
+            history.save(&path)?;
+        }
+        BenchCommand::Trend { day } => print!("{}", bench::render_trends(&history.trends(day))),
+        BenchCommand::Compare {
+            base,
+            head,
+            threshold,
+        } => {
+            let comparisons = history.compare(
+                &bench::resolve_commit(&base),
+                &bench::resolve_commit(&head),
+                threshold,
+            );
+            if comparisons.is_empty() {
+                miette::bail!("no benchmark was recorded at both `{base}` and `{head}`");
+            }
+            print!("{}", bench::render_comparisons(&comparisons));
+            let regressed = comparisons.iter().filter(|c| c.regression).count();
+            if regressed > 0 {
+                miette::bail!("{regressed} benchmark(s) regressed by more than {threshold}%");
+            }
+        }
+    }
+    Ok(())
+}
+
+fn heap(root: &Path, solutions: &[&dyn Solution], args: &HeapArgs) -> miette::Result<()> {
+    if let Some(dir) = &args.json {
+        std::fs::create_dir_all(dir)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("create `{}`", dir.display()))?;
+    }
+    let setup = bench::Setup::current();
+    let inputs_dir = args.inputs.dir(root);
+
+    let mut records = vec![];
+    for solution in solutions {
+        let path = input::input_path(inputs_dir, solution.day(), solution.part());
+        let input = match input::read(&path) {
+            Err(aoc_common::AocError::MissingInput { .. }) => {
+                eprintln!(
+                    "skipping {}: no input at `{}`",
+                    solution.id(),
+                    path.display()
+                );
+                continue;
+            }
+            input => input?,
+        };
+        let json = args.json.as_ref().map(|dir| {
+            dir.join(format!(
+                "day-{:02}-part{}.json",
+                solution.day(),
+                solution.part()
+            ))
+        });
+        // named outside of the profile, which only counts what the solution allocates
+        let name = path.display().to_string();
+        let (answer, usage) =
+            heap::measure(json.as_deref(), move || solve(*solution, &input, &name))?;
+        answer?;
+        records.push(heap::Record {
+            day: solution.day(),
+            part: solution.part(),
+            usage,
+            setup: setup.clone(),
+        });
+    }
+    if records.is_empty() {
+        miette::bail!("no selected solution has an input to profile");
+    }
+    print!("{}", heap::render(&records));
+
+    let path = root.join(heap::HEAP_PROFILE_FILE);
+    let mut profile = heap::Profile::load(&path)?;
+    profile.update(records);
+    profile.save(&path)?;
+    eprintln!("recorded at {} into `{}`", setup.commit, path.display());
+    Ok(())
+}
+
+// the file the spans are traced into, when asked for
+#[cfg(not(feature = "dhat-heap"))]
+fn chrome_trace(command: &Command) -> miette::Result<Option<std::fs::File>> {
+    let Command::Run(RunArgs {
+        trace: Some(path), ..
+    }) = command
+    else {
+        return Ok(None);
+    };
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("create `{}`", dir.display()))?;
+    }
+    let file = std::fs::File::create(path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("create trace file `{}`", path.display()))?;
+    Ok(Some(file))
+}
+
+/// Entry point of a year's `runner` binary, `root` is the directory holding the `day-XX` crates.
+pub fn main(registry: Registry, root: impl AsRef<Path>) -> miette::Result<()> {
+    let args = Args::parse();
+    let root = root.as_ref();
+    // the subscriber's own allocations would be counted against the solutions
+    #[cfg(not(feature = "dhat-heap"))]
+    let _guard = aoc_common::trace::init(chrome_trace(&args.command)?);
+    #[cfg(feature = "dhat-heap")]
+    if let Command::Run(RunArgs { trace: Some(_), .. }) = &args.command {
+        miette::bail!(
+            help = "run it from a build without the `dhat-heap` feature",
+            "the runner was built for heap profiling, which traces no span"
+        );
+    }
+
+    match args.command {
+        Command::List(selection) => {
+            for solution in registry.select((&selection).into()) {
+                println!("{}", solution.id());
+            }
+        }
+        Command::Run(args) => {
+            let solutions = registry
+                .select((&args.selection).into())
+                .collect::<Vec<_>>();
+            if solutions.is_empty() {
+                miette::bail!("no solution matches the selection");
+            }
+            run(root, &solutions, &args)?;
+        }
+        Command::Verify(args) => {
+            let solutions = registry
+                .select((&args.selection).into())
+                .collect::<Vec<_>>();
+            let outcomes = verify::verify(&solutions, args.inputs.dir(root), args.record)?;
+            print!("{}", verify::render(&outcomes));
+            let failed = outcomes
+                .iter()
+                .filter(|outcome| outcome.status() == verify::Status::Fail)
+                .count();
+            if failed > 0 {
+                miette::bail!("{failed} solution(s) failed verification");
+            }
+        }
+        Command::Examples(args) => {
+            let year = match args.year {
+                Some(year) => year,
+                None => registry_year(&registry)?,
+            };
+            extract_examples(root, year, args)?;
+        }
+        Command::Submit(args) => {
+            let year = match args.year {
+                Some(year) => year,
+                None => registry_year(&registry)?,
+            };
+            submit(&registry, root, year, args)?;
+        }
+        Command::Bench(command) => bench(root, command)?,
+        Command::Heap(args) => {
+            let solutions = registry
+                .select((&args.selection).into())
+                .collect::<Vec<_>>();
+            if solutions.is_empty() {
+                miette::bail!("no solution matches the selection");
+            }
+            heap(root, &solutions, &args)?;
+        }
+        Command::Fetch(args) => {
+            let year = match args.year {
+                Some(year) => year,
+                None => registry_year(&registry)?,
+            };
+            let inputs_dir = args.inputs.dir(root);
+            let fetcher = fetch::Fetcher::new(fetch::BASE_URL, args.session);
+            let path = input::input_path(inputs_dir, args.day, 1);
+            match fetcher.fetch(year, args.day, inputs_dir, args.force)? {
+                fetch::Fetched::Cached => println!(
+                    "`{}` is already there, pass `--force` to download it again",
+                    path.display()
+                ),
+                fetch::Fetched::Downloaded => {
+                    println!(
+                        "downloaded {year} day-{:02} into `{}`",
+                        args.day,
+                        path.display()
+                    )
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("day-01", Ok(1))]
+    #[case("day-25", Ok(25))]
+    #[case("7", Ok(7))]
+    #[case("day-", Err(()))]
+    #[case("day-01x", Err(()))]
+    #[case("part1", Err(()))]
+    fn test_parse_day(#[case] input: &str, #[case] expected: Result<u8, ()>) {
+        assert_eq!(expected, parse_day(input).map_err(|_| ()));
+    }
+
+    #[rstest]
+    #[case("part1", Ok(1))]
+    #[case("2", Ok(2))]
+    #[case("day-01", Err(()))]
+    fn test_parse_part(#[case] input: &str, #[case] expected: Result<u8, ()>) {
+        assert_eq!(expected, parse_part(input).map_err(|_| ()));
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Args::parse_from([
+            "runner",
+            "run",
+            "day-05",
+            "part2",
+            "--year",
+            "2023",
+            "--input",
+            "-",
+            "--trace",
+            "traces/day-05.json",
+        ]);
+        let Command::Run(RunArgs {
+            selection,
+            input,
+            trace,
+            ..
+        }) = args.command
+        else {
+            panic!("expected the run command");
+        };
+        assert_eq!(Some(PathBuf::from("-")), input);
+        assert_eq!(Some(PathBuf::from("traces/day-05.json")), trace);
+        assert_eq!(
+            Selector {
+                year: Some(2023),
+                day: Some(5),
+                part: Some(2)
+            },
+            Selector::from(&selection)
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Stand-in for the puzzle server, for tests only.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::mpsc,
+    thread,
+};
+
+/// A request as received by the stand-in server.
+#[derive(Debug)]
+pub struct Request {
+    /// The request line followed by the headers.
+    pub head: Vec<String>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.head.iter().skip(1).find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.eq_ignore_ascii_case(name).then_some(value.trim())
+        })
+    }
+}
+
+/// Answer each connection with the next `(status, body)` response and report the requests received.
+pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<Request>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for (status, body) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let head = reader
+                .by_ref()
+                .lines()
+                .map_while(Result::ok)
+                .map(|line| line.trim_end().to_string())
+                .take_while(|line| !line.is_empty())
+                .collect::<Vec<_>>();
+            let mut request = Request {
+                head,
+                body: String::new(),
+            };
+            let length = request
+                .header("content-length")
+                .and_then(|length| length.parse().ok())
+                .unwrap_or(0);
+            let mut content = vec![0; length];
+            reader.read_exact(&mut content).unwrap();
+            request.body = String::from_utf8_lossy(&content).into_owned();
+
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 {status} Whatever\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            sender.send(request).unwrap();
+        }
+    });
+    (url, receiver)
+}
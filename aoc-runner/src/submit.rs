@@ -0,0 +1,234 @@
+use std::{fmt::Display, time::Duration};
+
+use nom::{
+    bytes::complete::{tag, take_until},
+    character::complete::{one_of, space0, u64},
+    multi::many1,
+    sequence::{pair, preceded, terminated},
+    IResult,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::fetch::{excerpt, FetchError, Fetcher};
+
+/// The site's feedback on a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without telling in which direction.
+    Wrong,
+    /// An answer was submitted too recently, the next one is accepted after this long.
+    Wait(#[serde(with = "seconds")] Duration),
+    /// The part is already solved, or is not the one to solve next.
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Whether the answer itself was judged wrong.
+    pub fn is_rejection(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+// waits are stored as a number of seconds
+mod seconds {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(wait: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        wait.as_secs().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_secs)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "that's the right answer"),
+            Verdict::TooHigh => write!(f, "wrong answer, too high"),
+            Verdict::TooLow => write!(f, "wrong answer, too low"),
+            Verdict::Wrong => write!(f, "wrong answer"),
+            Verdict::Wait(wait) => write!(f, "answered too recently, wait {}s", wait.as_secs()),
+            Verdict::AlreadySolved => write!(f, "already solved, or not the part to solve next"),
+        }
+    }
+}
+
+impl Fetcher {
+    /// Submit `answer` to `part` of `day` of `year`.
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<Verdict, FetchError> {
+        let level = part.to_string();
+        let page = self.post_form(
+            &format!("/{year}/day/{day}/answer"),
+            &[("level", &level), ("answer", answer)],
+            year,
+            day,
+        )?;
+        parse_verdict(&page)
+    }
+}
+
+// the message of a response page, without its markup
+fn message(page: &str) -> String {
+    let article = match page.split_once("<article") {
+        Some((_, rest)) => {
+            let rest = rest.split_once('>').map_or(rest, |(_, rest)| rest);
+            rest.split_once("</article>")
+                .map_or(rest, |(article, _)| article)
+        }
+        None => page,
+    };
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// e.g. `You have 1m 12s left to wait`
+fn parse_wait(input: &str) -> IResult<&str, Duration> {
+    let (input, _) = take_until("You have ")(input)?;
+    let (input, units) = preceded(
+        tag("You have "),
+        terminated(
+            many1(terminated(pair(u64, one_of("hms")), space0)),
+            tag("left to wait"),
+        ),
+    )(input)?;
+    let seconds = units
+        .iter()
+        .map(|(value, unit)| match unit {
+            'h' => value * 3600,
+            'm' => value * 60,
+            _ => *value,
+        })
+        .sum();
+    Ok((input, Duration::from_secs(seconds)))
+}
+
+/// Read the verdict out of the page answering a submission.
+pub fn parse_verdict(page: &str) -> Result<Verdict, FetchError> {
+    let message = message(page);
+    let verdict = if message.contains("That's the right answer") {
+        Verdict::Correct
+    } else if message.contains("That's not the right answer") {
+        if message.contains("too high") {
+            Verdict::TooHigh
+        } else if message.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if message.contains("You gave an answer too recently") {
+        let (_, wait) = parse_wait(&message).map_err(|_| FetchError::UnknownVerdict {
+            excerpt: excerpt(&message),
+        })?;
+        Verdict::Wait(wait)
+    } else if message.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else {
+        return Err(FetchError::UnknownVerdict {
+            excerpt: excerpt(&message),
+        });
+    };
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stand_in::serve;
+    use rstest::rstest;
+
+    fn page(message: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html><body><main>\n<article><p>{message}</p></article>\n</main></body></html>"
+        )
+    }
+
+    #[rstest]
+    #[case(
+        "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to restoring snow operations.",
+        Verdict::Correct
+    )]
+    #[case(
+        "That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again.",
+        Verdict::TooHigh
+    )]
+    #[case(
+        "That's not the right answer; your answer is too low.  Please wait one minute before trying again.",
+        Verdict::TooLow
+    )]
+    #[case(
+        "That's not the right answer.  If you're stuck, there are some general tips on the <a href=\"/2023/about\">about page</a>.",
+        Verdict::Wrong
+    )]
+    #[case(
+        "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 39s left to wait. <a href=\"/2023/day/5\">[Return to Day 5]</a>",
+        Verdict::Wait(Duration::from_secs(39))
+    )]
+    #[case(
+        "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 12s left to wait.",
+        Verdict::Wait(Duration::from_secs(252))
+    )]
+    #[case(
+        "You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2023/day/5\">[Return to Day 5]</a>",
+        Verdict::AlreadySolved
+    )]
+    fn test_parse_verdict(#[case] message: &str, #[case] expected: Verdict) -> miette::Result<()> {
+        assert_eq!(expected, parse_verdict(&page(message))?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_unknown_verdict() {
+        let err = parse_verdict(&page("Something <em>else</em> happened.")).unwrap_err();
+        assert_eq!(
+            "unexpected answer to a submission: Something else happened.",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_submit() -> miette::Result<()> {
+        use miette::IntoDiagnostic;
+
+        let correct =
+            "<html><main><article><p>That's the right answer!</p></article></main></html>";
+        let (url, requests) = serve(vec![(200, correct)]);
+        let fetcher = Fetcher::new(url, Some("cookie".to_string()));
+        assert_eq!(Verdict::Correct, fetcher.submit(2023, 5, 2, "46")?);
+
+        let request = requests.recv().into_diagnostic()?;
+        assert_eq!("POST /2023/day/5/answer HTTP/1.1", request.head[0]);
+        assert_eq!(Some("session=cookie"), request.header("cookie"));
+        assert_eq!("level=2&answer=46", request.body);
+        Ok(())
+    }
+
+    #[test]
+    fn test_submit_needs_a_session() {
+        let fetcher = Fetcher::new(crate::fetch::BASE_URL, None);
+        assert!(matches!(
+            fetcher.submit(2023, 5, 1, "35"),
+            Err(FetchError::NoSession)
+        ));
+    }
+}
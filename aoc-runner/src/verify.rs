@@ -0,0 +1,260 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use aoc_common::{input, AocError, Solution, SolutionId};
+
+use crate::answers::{Answers, ANSWERS_FILE};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Status::Pass => "pass",
+            Status::Fail => "fail",
+            Status::Missing => "missing",
+        })
+    }
+}
+
+/// What a solver produced for its input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Computed {
+    Answer(String),
+    Error(String),
+    NoInput,
+}
+
+#[derive(Debug)]
+pub struct Outcome {
+    pub id: SolutionId,
+    pub computed: Computed,
+    pub expected: Option<String>,
+    pub elapsed: Duration,
+}
+
+impl Outcome {
+    pub fn status(&self) -> Status {
+        match (&self.computed, &self.expected) {
+            (Computed::NoInput, _) | (Computed::Answer(_), None) => Status::Missing,
+            (Computed::Error(_), _) => Status::Fail,
+            (Computed::Answer(answer), Some(expected)) if answer == expected => Status::Pass,
+            (Computed::Answer(_), Some(_)) => Status::Fail,
+        }
+    }
+}
+
+/// Run `solution` on `input` and compare its answer to the `expected` one.
+pub fn check(
+    solution: &dyn Solution,
+    input: Result<String, AocError>,
+    expected: Option<&str>,
+) -> Outcome {
+    let start = Instant::now();
+    let computed = match input {
+        Err(AocError::MissingInput { .. }) => Computed::NoInput,
+        Err(e) => Computed::Error(e.to_string()),
+        Ok(input) => match solution.solve(&input) {
+            Ok(answer) => Computed::Answer(answer),
+            Err(e) => Computed::Error(e.to_string()),
+        },
+    };
+    Outcome {
+        id: solution.id(),
+        computed,
+        expected: expected.map(str::to_string),
+        elapsed: start.elapsed(),
+    }
+}
+
+/// Check every solution against the answers stored in `inputs_dir`.
+///
+/// With `record`, answers of solutions that have none yet are written to the answers files.
+pub fn verify(
+    solutions: &[&dyn Solution],
+    inputs_dir: &Path,
+    record: bool,
+) -> miette::Result<Vec<Outcome>> {
+    let mut answers = BTreeMap::new();
+    let mut recorded = BTreeSet::new();
+    let mut outcomes = Vec::with_capacity(solutions.len());
+
+    for solution in solutions {
+        let path = input::input_path(inputs_dir, solution.day(), solution.part());
+        let day_dir = path.parent().expect("input lives in a day directory");
+        let key = input_key(&path);
+        if !answers.contains_key(day_dir) {
+            let loaded = Answers::load(&day_dir.join(ANSWERS_FILE))?;
+            answers.insert(day_dir.to_path_buf(), loaded);
+        }
+        let day_answers = answers.get_mut(day_dir).expect("answers were just loaded");
+
+        let outcome = check(
+            *solution,
+            input::read(&path),
+            day_answers.get(&key, solution.part()),
+        );
+        if let (true, Status::Missing, Computed::Answer(answer)) =
+            (record, outcome.status(), &outcome.computed)
+        {
+            day_answers.set(&key, solution.part(), answer.as_str());
+            recorded.insert(day_dir.to_path_buf());
+        }
+        outcomes.push(outcome);
+    }
+
+    for day_dir in recorded {
+        answers[&day_dir].save(&day_dir.join(ANSWERS_FILE))?;
+    }
+    Ok(outcomes)
+}
+
+/// Answers are keyed by the input file name without its extension.
+pub fn input_key(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Render the outcomes as a table followed by a summary line.
+pub fn render(outcomes: &[Outcome]) -> String {
+    let header = ["solution", "status", "answer", "expected", "time"].map(String::from);
+    let rows = outcomes
+        .iter()
+        .map(|outcome| {
+            let answer = match &outcome.computed {
+                Computed::Answer(answer) => answer.clone(),
+                Computed::Error(e) => format!("error: {}", e.lines().next().unwrap_or_default()),
+                Computed::NoInput => "no input".to_string(),
+            };
+            [
+                outcome.id.to_string(),
+                outcome.status().to_string(),
+                answer,
+                outcome.expected.clone().unwrap_or_else(|| "-".to_string()),
+                format!("{:.2?}", outcome.elapsed),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let mut widths = header.each_ref().map(String::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    for row in std::iter::once(&header).chain(&rows) {
+        let line = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+
+    let count = |status| outcomes.iter().filter(|o| o.status() == status).count();
+    table.push_str(&format!(
+        "{} passed, {} failed, {} missing\n",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Missing)
+    ));
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solver;
+    use rstest::rstest;
+
+    fn length(input: &str) -> Result<String, AocError> {
+        match input {
+            "" => Err(AocError::LogicError("empty input".to_string())),
+            input => Ok(input.len().to_string()),
+        }
+    }
+
+    static SOLVER: Solver = Solver {
+        year: 2023,
+        day: 1,
+        part: 2,
+        process: length,
+    };
+
+    #[rstest]
+    #[case(Ok("abc"), Some("3"), Status::Pass)]
+    #[case(Ok("abc"), Some("4"), Status::Fail)]
+    #[case(Ok("abc"), None, Status::Missing)]
+    #[case(Ok(""), Some("0"), Status::Fail)]
+    #[case(Err(()), Some("3"), Status::Missing)]
+    fn test_check(
+        #[case] input: Result<&str, ()>,
+        #[case] expected: Option<&str>,
+        #[case] status: Status,
+    ) {
+        let input = input
+            .map(str::to_string)
+            .map_err(|_| AocError::MissingInput {
+                path: "day-01/input2.txt".into(),
+            });
+        assert_eq!(status, check(&SOLVER, input, expected).status());
+    }
+
+    #[test]
+    fn test_verify_and_record() -> miette::Result<()> {
+        use miette::IntoDiagnostic;
+
+        let dir = tempfile::tempdir().into_diagnostic()?;
+        let day_dir = dir.path().join("day-01");
+        std::fs::create_dir(&day_dir).into_diagnostic()?;
+        std::fs::write(day_dir.join("input2.txt"), "hello").into_diagnostic()?;
+
+        let outcomes = verify(&[&SOLVER], dir.path(), false)?;
+        assert_eq!(Status::Missing, outcomes[0].status());
+        assert!(!day_dir.join(ANSWERS_FILE).exists());
+
+        verify(&[&SOLVER], dir.path(), true)?;
+        let outcomes = verify(&[&SOLVER], dir.path(), false)?;
+        assert_eq!(Status::Pass, outcomes[0].status());
+        assert_eq!(Some("5".to_string()), outcomes[0].expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_render() {
+        let outcome = |computed, expected: Option<&str>| Outcome {
+            id: SOLVER.id(),
+            computed,
+            expected: expected.map(str::to_string),
+            elapsed: Duration::from_micros(1500),
+        };
+        let outcomes = [
+            outcome(Computed::Answer("3".to_string()), Some("3")),
+            outcome(Computed::Error("bad\ninput".to_string()), Some("3")),
+            outcome(Computed::NoInput, None),
+        ];
+        assert_eq!(
+            "solution           status   answer      expected  time
+2023 day-01 part2  pass     3           3         1.50ms
+2023 day-01 part2  fail     error: bad  3         1.50ms
+2023 day-01 part2  missing  no input    -         1.50ms
+1 passed, 1 failed, 1 missing
+",
+            render(&outcomes)
+        );
+    }
+}